@@ -1,12 +1,14 @@
 mod answer;
 pub use answer::*;
+mod params;
+pub use params::*;
 mod puzzle;
 pub use puzzle::*;
 mod solution_collection;
 pub use solution_collection::*;
 mod solution_wrapper;
 pub use solution_wrapper::*;
-mod timer;
+pub mod timer;
 
 pub mod tools;
 
@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+/// Parameter of a solution, declared with `#[param(default = ...)]` on a field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub default: String,
+    pub value: String,
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} = {}", self.name, self.type_name, self.value)?;
+        if self.value != self.default {
+            write!(f, " (default {})", self.default)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of a solution, implemented by `#[aoc_puzzle]`
+pub trait PuzzleParams {
+    fn params(&self) -> Vec<Param>;
+    /// Set a parameter from its text value, like `space=11,7` on the command line
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String>;
+}
+
+/// Value of a parameter which can be written as text, tuples are separated by commas
+pub trait ParamValue: Sized {
+    fn from_param(value: &str) -> Result<Self, String>;
+    fn to_param(&self) -> String;
+}
+
+macro_rules! param_value_from_str {
+    ($($type:ty),+) => {
+        $(
+            impl ParamValue for $type {
+                fn from_param(value: &str) -> Result<Self, String> {
+                    value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("'{}' is not a valid {}", value, stringify!($type)))
+                }
+                fn to_param(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )+
+    };
+}
+param_value_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char
+);
+
+impl ParamValue for String {
+    fn from_param(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+    fn to_param(&self) -> String {
+        self.clone()
+    }
+}
+
+macro_rules! param_value_tuple {
+    ($len:literal, $($name:ident: $index:tt),+) => {
+        impl<$($name: ParamValue),+> ParamValue for ($($name,)+) {
+            fn from_param(value: &str) -> Result<Self, String> {
+                let parts = value.split(',').collect::<Vec<_>>();
+                if parts.len() != $len {
+                    return Err(format!("'{}' needs {} comma separated values", value, $len));
+                }
+                Ok(($($name::from_param(parts[$index])?,)+))
+            }
+            fn to_param(&self) -> String {
+                [$(self.$index.to_param()),+].join(",")
+            }
+        }
+    };
+}
+param_value_tuple!(2, A: 0, B: 1);
+param_value_tuple!(3, A: 0, B: 1, C: 2);
+param_value_tuple!(4, A: 0, B: 1, C: 2, D: 3);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_values() {
+        assert_eq!(usize::from_param("12"), Ok(12));
+        assert_eq!(<(i32, i32)>::from_param("11, 7"), Ok((11, 7)));
+        assert_eq!((101, 103).to_param(), "101,103");
+        assert_eq!(
+            u8::from_param("-1"),
+            Err("'-1' is not a valid u8".to_string())
+        );
+        assert_eq!(
+            <(i32, i32)>::from_param("11"),
+            Err("'11' needs 2 comma separated values".to_string())
+        );
+    }
+
+    #[test]
+    fn should_display_param() {
+        let mut param = Param {
+            name: "space",
+            type_name: "(i32, i32)",
+            default: "101,103".to_string(),
+            value: "101,103".to_string(),
+        };
+        assert_eq!(param.to_string(), "space: (i32, i32) = 101,103");
+        param.value = "11,7".to_string();
+        assert_eq!(
+            param.to_string(),
+            "space: (i32, i32) = 11,7 (default 101,103)"
+        );
+    }
+}
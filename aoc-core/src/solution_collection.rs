@@ -1,92 +1,468 @@
-use crate::{Answer, RunnableSolution};
-use std::{collections::HashMap, time::Duration};
+use crate::{
+    timer::{profiled, DayTimes, ProfileTree, TimingStats},
+    Answer, Param, ParsedInput, Puzzle, RunnableSolution,
+};
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
 
 #[derive(Default)]
 pub struct SolutionCollection {
     solutions: HashMap<u32, Box<dyn RunnableSolution>>,
+    profiling: bool,
+    repetitions: Repetitions,
+}
+
+/// How often every phase of a day runs, see [`SolutionCollection::set_repetitions`]
+#[derive(Debug, Clone, Copy)]
+struct Repetitions {
+    repeat: usize,
+    warmup: usize,
+}
+
+impl Default for Repetitions {
+    fn default() -> Self {
+        Self {
+            repeat: 1,
+            warmup: 0,
+        }
+    }
 }
 
 impl SolutionCollection {
     pub fn register_solution(&mut self, solution: Box<dyn RunnableSolution>) {
-        self.solutions.insert(solution.get_day(), solution);
+        let day = solution.get_day();
+        if self.solutions.insert(day, solution).is_some() {
+            panic!("Day {} has more than one solution", day);
+        }
     }
 
-    pub fn run(&self, day: Option<u32>) {
+    /// Print a tree of the [`span!`](crate::span)s of every phase after running a day
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+    }
+
+    /// Run every phase `warmup` times untimed and then `repeat` times timed on the
+    /// loaded puzzle, reporting the median and statistics of the timed runs
+    ///
+    /// Answers which differ between runs are reported as non-deterministic.
+    pub fn set_repetitions(&mut self, repeat: NonZeroUsize, warmup: usize) {
+        self.repetitions = Repetitions {
+            repeat: repeat.get(),
+            warmup,
+        };
+    }
+
+    /// Run the day or all days, returns the times of every day for a [`Budget`](crate::timer::Budget)
+    pub fn run(&self, day: Option<u32>) -> Vec<DayTimes> {
         if let Some(day) = day {
-            self.run_day(&day);
-        } else {
-            let mut days = self.solutions.keys().collect::<Vec<_>>();
-            days.sort();
-            let mut total_time = Duration::default();
-            for day in days {
-                total_time += self.run_day(day);
-            }
-            println!("total_time: {:.2?}", total_time);
+            return vec![self.run_day(&day)];
         }
+        let mut days = self.solutions.keys().collect::<Vec<_>>();
+        days.sort();
+        let times = days
+            .into_iter()
+            .map(|day| self.run_day(day))
+            .collect::<Vec<_>>();
+        let total_time = times.iter().map(|day| day.total()).sum::<Duration>();
+        println!("total_time: {:.2?}", total_time);
+        times
     }
 
-    fn run_day(&self, day: &u32) -> Duration {
+    fn run_day(&self, day: &u32) -> DayTimes {
         if !self.solutions.contains_key(day) {
             panic!("Day {} was not yet created", day);
         }
         let solution = &self.solutions.get(day).unwrap();
         let puzzle = solution.get_puzzle(); // Preload puzzle
         println!("Day {}", day);
-        let (part1, time1) = crate::timed!(solution.part1(&puzzle));
-        let (part2, time2) = crate::timed!(solution.part2(&puzzle));
-        println!("Part 1: {}", display_answer(part1));
-        println!("Part 2: {}", display_answer(part2));
+        let mut phases = PhaseTimer {
+            profiling: self.profiling,
+            repetitions: self.repetitions,
+            profiles: vec![],
+            stats: vec![],
+        };
+        let parsed = phases.time(
+            "parse",
+            || solution.parse(&puzzle),
+            |a, b| a.is_ok() == b.is_ok(),
+        );
+        if parsed.differing.is_some() {
+            println!("Parsing is non-deterministic, it failed on some runs only");
+        }
+        let parse_time = parsed.time;
+        let solved = match parsed.result {
+            Ok(parsed) => solve(solution.as_ref(), &parsed, &mut phases),
+            Err(e) => Solved {
+                answers: [parse_failure(&e), parse_failure(&e)],
+                differing: [None, None],
+                time: SolveTime::Separate(Duration::default(), Duration::default()),
+            },
+        };
+        for (part, (answer, differing)) in solved.answers.iter().zip(&solved.differing).enumerate()
+        {
+            match differing {
+                Some(differing) => println!(
+                    "Part {}: {} (non-deterministic, another run gave {})",
+                    part + 1,
+                    display_answer(answer),
+                    display_answer(differing)
+                ),
+                None => println!("Part {}: {}", part + 1, display_answer(answer)),
+            }
+        }
+        let total = parse_time + solved.time.total();
         println!(
-            "time: {:.2?} (1: {:.2?}, 2: {:.2?})",
-            time1 + time2,
-            time1,
-            time2
+            "time: {:.2?} (parse: {:.2?}, {})",
+            total, parse_time, solved.time
         );
-        time1 + time2
+        if self.repetitions.repeat > 1 {
+            println!("median of {} runs:", self.repetitions.repeat);
+            for (name, stats) in &phases.stats {
+                println!("  {}: {}", name, stats);
+            }
+        }
+        for profile in phases.profiles {
+            print!("{}", profile);
+        }
+        DayTimes {
+            day: *day,
+            parse: parse_time,
+            parts: solved.time.parts(),
+        }
     }
 
+    /// Check that `solve_both` gives the same answers as `part1` and `part2`, for
+    /// the day or for all days, returns false when any day disagrees
+    pub fn check(&self, day: Option<u32>) -> bool {
+        let mut days = match day {
+            Some(day) => vec![day],
+            None => self.get_days(),
+        };
+        days.sort();
+        let mut agrees = true;
+        for day in days {
+            let Some(solution) = self.solutions.get(&day) else {
+                panic!("Day {} was not yet created", day);
+            };
+            let parsed = match solution.parse(&solution.get_puzzle()) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    println!("Day {}: {}", day, display_answer(&parse_failure(&e)));
+                    agrees = false;
+                    continue;
+                }
+            };
+            let Some((both1, both2)) = solution.solve_both(&parsed) else {
+                println!("Day {}: no solve_both", day);
+                continue;
+            };
+            let separate = [solution.part1(&parsed), solution.part2(&parsed)];
+            let mut day_agrees = true;
+            for (part, (both, separate)) in [both1, both2].iter().zip(separate.iter()).enumerate() {
+                if both != separate {
+                    println!(
+                        "Day {}: part {} differs, solve_both: {}, part{}: {}",
+                        day,
+                        part + 1,
+                        display_answer(both),
+                        part + 1,
+                        display_answer(separate)
+                    );
+                    day_agrees = false;
+                }
+            }
+            if day_agrees {
+                println!("Day {}: solve_both agrees", day);
+            }
+            agrees &= day_agrees;
+        }
+        agrees
+    }
+
+    /// Answer and time of part 1, parsing is not included in the time
     pub fn run_day_part1(&self, day: &u32) -> (Answer, std::time::Duration) {
         let solution = &self.solutions.get(day).unwrap();
         let puzzle = solution.get_puzzle(); // Preload puzzle
-        crate::timed!(solution.part1(&puzzle))
+        match solution.parse(&puzzle) {
+            Ok(parsed) => crate::timed!(solution.part1(&parsed)),
+            Err(e) => (parse_failure(&e), Duration::default()),
+        }
     }
+    /// Answer and time of part 2, parsing is not included in the time
     pub fn run_day_part2(&self, day: &u32) -> (Answer, std::time::Duration) {
         let solution = &self.solutions.get(day).unwrap();
         let puzzle = solution.get_puzzle(); // Preload puzzle
-        crate::timed!(solution.part2(&puzzle))
+        match solution.parse(&puzzle) {
+            Ok(parsed) => crate::timed!(solution.part2(&parsed)),
+            Err(e) => (parse_failure(&e), Duration::default()),
+        }
     }
 
-    pub fn prepare_bench(
-        &self,
-        day: &u32,
-    ) -> (impl Fn() -> Answer + use<'_>, impl Fn() -> Answer + use<'_>) {
+    /// Load and parse the puzzle of the day, so every phase can be benchmarked on its own
+    pub fn prepare_bench(&self, day: &u32) -> PreparedBench<'_> {
         let solution = self.solutions.get(day).unwrap();
-        let puzzle1 = solution.get_puzzle(); // Preload puzzle
-        let puzzle2 = puzzle1.clone();
-        (
-            move || solution.part1(&puzzle1),
-            move || solution.part2(&puzzle2),
-        )
+        let puzzle = solution.get_puzzle(); // Preload puzzle
+        let parsed = solution
+            .parse(&puzzle)
+            .unwrap_or_else(|e| panic!("Failed to parse input of day {}: {}", day, e));
+        PreparedBench {
+            solution: solution.as_ref(),
+            puzzle,
+            parsed,
+        }
     }
 
     pub fn get_days(&self) -> Vec<u32> {
         self.solutions.keys().copied().collect()
     }
+
+    pub fn get_params(&self, day: &u32) -> Vec<Param> {
+        self.solutions
+            .get(day)
+            .map(|solution| solution.params())
+            .unwrap_or_default()
+    }
+
+    /// Set a parameter of the day, or of every day which has it when no day is given
+    pub fn set_param(&mut self, day: Option<u32>, name: &str, value: &str) -> Result<(), String> {
+        if let Some(day) = day {
+            let solution = self
+                .solutions
+                .get_mut(&day)
+                .ok_or_else(|| format!("Day {} was not yet created", day))?;
+            return solution
+                .set_param(name, value)
+                .map_err(|e| format!("Day {}: {}", day, e));
+        }
+        let mut found = false;
+        for (day, solution) in self.solutions.iter_mut() {
+            if solution.params().iter().any(|p| p.name == name) {
+                solution
+                    .set_param(name, value)
+                    .map_err(|e| format!("Day {}: {}", day, e))?;
+                found = true;
+            }
+        }
+        match found {
+            true => Ok(()),
+            false => Err(format!("No day has a parameter '{}'", name)),
+        }
+    }
+
+    /// Print the parameters of the day, or of all days
+    pub fn list_params(&self, day: Option<u32>) {
+        let mut days = match day {
+            Some(day) => vec![day],
+            None => self.get_days(),
+        };
+        days.sort();
+        for day in days {
+            let params = self.get_params(&day);
+            if !params.is_empty() {
+                println!("Day {}", day);
+                for param in params {
+                    println!("  {}", param);
+                }
+            }
+        }
+    }
+}
+
+/// Puzzle of a day with its parsed input, see [`SolutionCollection::prepare_bench`]
+pub struct PreparedBench<'a> {
+    solution: &'a dyn RunnableSolution,
+    puzzle: Puzzle,
+    parsed: ParsedInput,
+}
+
+impl PreparedBench<'_> {
+    pub fn parse(&self) -> ParsedInput {
+        self.solution.parse(&self.puzzle).unwrap()
+    }
+    pub fn part1(&self) -> Answer {
+        self.solution.part1(&self.parsed)
+    }
+    pub fn part2(&self) -> Answer {
+        self.solution.part2(&self.parsed)
+    }
+    pub fn solve_both(&self) -> Option<(Answer, Answer)> {
+        self.solution.solve_both(&self.parsed)
+    }
+    /// Both parts the way the runner solves them, in one pass when the day has `solve_both`
+    pub fn solve(&self) -> (Answer, Answer) {
+        self.solve_both()
+            .unwrap_or_else(|| (self.part1(), self.part2()))
+    }
+    /// Size of the puzzle input in bytes
+    pub fn input_size(&self) -> usize {
+        self.puzzle.input_as_str().len()
+    }
 }
 
-fn display_answer(answer: Answer) -> String {
+/// Time spent on the parts, a single time when both were solved in one pass
+enum SolveTime {
+    Separate(Duration, Duration),
+    Combined(Duration),
+}
+
+impl SolveTime {
+    fn total(&self) -> Duration {
+        match self {
+            Self::Separate(time1, time2) => *time1 + *time2,
+            Self::Combined(time) => *time,
+        }
+    }
+
+    fn parts(&self) -> Vec<(&'static str, Duration)> {
+        match self {
+            Self::Separate(time1, time2) => vec![("part1", *time1), ("part2", *time2)],
+            Self::Combined(time) => vec![("1+2", *time)],
+        }
+    }
+}
+
+impl std::fmt::Display for SolveTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Separate(time1, time2) => write!(f, "1: {:.2?}, 2: {:.2?}", time1, time2),
+            Self::Combined(time) => write!(f, "1+2: {:.2?}", time),
+        }
+    }
+}
+
+/// Answers of both parts, with the first different answer of another run
+struct Solved {
+    answers: [Answer; 2],
+    differing: [Option<Answer>; 2],
+    time: SolveTime,
+}
+
+/// Solve both parts, using `solve_both` when the solution has it
+fn solve(solution: &dyn RunnableSolution, parsed: &ParsedInput, phases: &mut PhaseTimer) -> Solved {
+    let both = phases.time("1+2", || solution.solve_both(parsed), |a, b| a == b);
+    if let Some((part1, part2)) = both.result {
+        let (differing1, differing2) = both.differing.flatten().unzip();
+        return Solved {
+            differing: [
+                differing1.filter(|answer| *answer != part1),
+                differing2.filter(|answer| *answer != part2),
+            ],
+            answers: [part1, part2],
+            time: SolveTime::Combined(both.time),
+        };
+    }
+    // The solution has no `solve_both`, its profile and timings are empty
+    phases.discard("1+2");
+    let part1 = phases.time("part1", || solution.part1(parsed), |a, b| a == b);
+    let part2 = phases.time("part2", || solution.part2(parsed), |a, b| a == b);
+    Solved {
+        answers: [part1.result, part2.result],
+        differing: [part1.differing, part2.differing],
+        time: SolveTime::Separate(part1.time, part2.time),
+    }
+}
+
+/// Times the phases of a day, with a profile of every phase when profiling
+struct PhaseTimer {
+    profiling: bool,
+    repetitions: Repetitions,
+    profiles: Vec<ProfileTree>,
+    /// Timings of the phases which ran more than once
+    stats: Vec<(&'static str, TimingStats)>,
+}
+
+/// Result of a phase and its median time
+struct Measured<R> {
+    result: R,
+    time: Duration,
+    /// First result of another run which is not the same as `result`
+    differing: Option<R>,
+}
+
+impl PhaseTimer {
+    /// Run a phase the warmup and repeat times, the first timed run is profiled
+    fn time<R>(
+        &mut self,
+        name: &'static str,
+        mut f: impl FnMut() -> R,
+        same: impl Fn(&R, &R) -> bool,
+    ) -> Measured<R> {
+        let Repetitions { repeat, warmup } = self.repetitions;
+        let mut result = None;
+        let mut differing = None;
+        let mut times = Vec::with_capacity(repeat);
+        for run in 0..warmup + repeat {
+            let (run_result, time) = match self.profiling && run == warmup {
+                true => {
+                    let ((run_result, time), profile) = profiled(name, || crate::timed!(f()));
+                    self.profiles.push(profile);
+                    (run_result, time)
+                }
+                false => crate::timed!(f()),
+            };
+            if run >= warmup {
+                times.push(time);
+            }
+            match &result {
+                None => result = Some(run_result),
+                Some(first) if differing.is_none() && !same(first, &run_result) => {
+                    differing = Some(run_result)
+                }
+                Some(_) => {}
+            }
+        }
+        let stats = TimingStats::new(times).expect("A phase runs at least once");
+        let time = stats.median();
+        if stats.runs() > 1 {
+            self.stats.push((name, stats));
+        }
+        Measured {
+            result: result.expect("A phase runs at least once"),
+            time,
+            differing,
+        }
+    }
+
+    /// Forget the profile and timings of a phase
+    fn discard(&mut self, name: &str) {
+        self.profiles.retain(|profile| profile.root().name != name);
+        self.stats.retain(|(phase, _)| *phase != name);
+    }
+}
+
+fn parse_failure(error: &str) -> Answer {
+    Err::<String, _>(format!("Failed to parse input: {}", error)).into()
+}
+
+fn display_answer(answer: &Answer) -> String {
     match answer.get_result() {
         Ok(result) => result,
         Err(e) => e,
     }
 }
 
+/// Fails the build when two solutions have the same day, see [`setup_solutions!`]
+pub const fn assert_unique_days(days: &[u32]) {
+    let mut i = 0;
+    while i < days.len() {
+        let mut j = i + 1;
+        while j < days.len() {
+            if days[i] == days[j] {
+                panic!("Two solutions have the same day, check the `day` of the `aoc_puzzle`s");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
 /// Imports all the puzzle modules and returns a run function which can be called to run puzzles.
 #[macro_export]
 macro_rules! setup_solutions {
     ($($x:ident),+) => {
         aoc_core::include_solution_mod!($($x),+);
 
+        const _: () = aoc_core::assert_unique_days(&[$($x::DAY),+]);
+
         pub fn get_collection() -> aoc_core::SolutionCollection {
             let mut puzzles = aoc_core::SolutionCollection::default();
             aoc_core::register_solution!(puzzles, $($x),+);
@@ -103,7 +479,7 @@ macro_rules! setup_solutions {
 #[macro_export(local_inner_macros)]
 macro_rules! include_solution_mod {
     ($x:ident) => {
-        mod $x;
+        pub mod $x;
     };
     ($x:ident, $($y:ident),+) => (
         aoc_core::include_solution_mod!($x);
@@ -121,3 +497,48 @@ macro_rules! register_solution {
         aoc_core::register_solution!($collection, $($y),+);
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase_timer(repeat: usize, warmup: usize) -> PhaseTimer {
+        PhaseTimer {
+            profiling: false,
+            repetitions: Repetitions { repeat, warmup },
+            profiles: vec![],
+            stats: vec![],
+        }
+    }
+
+    #[test]
+    fn repeats_phases() {
+        let mut phases = phase_timer(5, 2);
+        let mut runs = 0;
+        let measured = phases.time("part1", || runs += 1, |a, b| a == b);
+        assert_eq!(runs, 7);
+        assert!(measured.differing.is_none());
+        assert_eq!(phases.stats.len(), 1);
+        assert_eq!(phases.stats[0].1.runs(), 5);
+        phases.discard("part1");
+        assert!(phases.stats.is_empty());
+    }
+
+    #[test]
+    fn flags_non_deterministic() {
+        let mut phases = phase_timer(3, 1);
+        let mut runs = 0;
+        let measured = phases.time(
+            "part2",
+            || {
+                runs += 1;
+                runs.min(3)
+            },
+            |a, b| a == b,
+        );
+        assert_eq!(measured.result, 1);
+        assert_eq!(measured.differing, Some(2));
+        let single = phase_timer(1, 0).time("parse", || 1, |a, b| a == b);
+        assert!(single.differing.is_none());
+    }
+}
@@ -1,20 +1,69 @@
-use crate::{Answer, Puzzle};
+use std::any::Any;
+
+use crate::{Answer, Param, Puzzle, PuzzleParams};
+
+/// Parsed input of a solution, see [`ParsedPuzzleSolution::Parsed`]
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
 
 pub trait RunnableSolution: Send + Sync {
     fn get_puzzle(&self) -> Puzzle;
-    fn part1(&self, puzzle: &Puzzle) -> Answer;
-    fn part2(&self, puzzle: &Puzzle) -> Answer;
+    fn parse(&self, puzzle: &Puzzle) -> Result<ParsedInput, String>;
+    fn part1(&self, parsed: &ParsedInput) -> Answer;
+    fn part2(&self, parsed: &ParsedInput) -> Answer;
+    fn solve_both(&self, parsed: &ParsedInput) -> Option<(Answer, Answer)>;
     fn get_day(&self) -> u32;
+    fn params(&self) -> Vec<Param>;
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String>;
 }
 
 pub trait PuzzleSolution: Send + Sync {
     fn part1(&self, puzzle: &Puzzle) -> Answer;
     fn part2(&self, puzzle: &Puzzle) -> Answer;
+    /// Both answers in a single pass, for days where the parts share most of the work
+    ///
+    /// The runner prefers this over `part1` and `part2` when it returns `Some`.
+    fn solve_both(&self, _puzzle: &Puzzle) -> Option<(Answer, Answer)> {
+        None
+    }
+}
+
+/// Solution which parses the input once, both parts share the parsed input
+///
+/// Every [`PuzzleSolution`] is a `ParsedPuzzleSolution` which parses to the puzzle itself.
+pub trait ParsedPuzzleSolution: Send + Sync {
+    type Parsed: Send + Sync + 'static;
+    fn parse(&self, puzzle: &Puzzle) -> Result<Self::Parsed, Box<dyn std::error::Error>>;
+    fn part1(&self, parsed: &Self::Parsed) -> Answer;
+    fn part2(&self, parsed: &Self::Parsed) -> Answer;
+    /// See [`PuzzleSolution::solve_both`]
+    fn solve_both(&self, _parsed: &Self::Parsed) -> Option<(Answer, Answer)> {
+        None
+    }
+}
+
+impl<S: PuzzleSolution> ParsedPuzzleSolution for S {
+    type Parsed = Puzzle;
+
+    fn parse(&self, puzzle: &Puzzle) -> Result<Puzzle, Box<dyn std::error::Error>> {
+        Ok(puzzle.clone())
+    }
+
+    fn part1(&self, puzzle: &Puzzle) -> Answer {
+        PuzzleSolution::part1(self, puzzle)
+    }
+
+    fn part2(&self, puzzle: &Puzzle) -> Answer {
+        PuzzleSolution::part2(self, puzzle)
+    }
+
+    fn solve_both(&self, puzzle: &Puzzle) -> Option<(Answer, Answer)> {
+        PuzzleSolution::solve_both(self, puzzle)
+    }
 }
 
 pub struct SolutionWrapper<S>
 where
-    S: PuzzleSolution,
+    S: ParsedPuzzleSolution + PuzzleParams,
 {
     solution: S,
     props: SolutionProps,
@@ -27,30 +76,56 @@ pub struct SolutionProps {
 
 impl<S> SolutionWrapper<S>
 where
-    S: PuzzleSolution,
+    S: ParsedPuzzleSolution + PuzzleParams,
 {
     pub fn new(solution: S, props: SolutionProps) -> Self {
         Self { solution, props }
     }
+
+    fn downcast<'a>(&self, parsed: &'a ParsedInput) -> &'a S::Parsed {
+        parsed
+            .as_ref()
+            .downcast_ref()
+            .unwrap_or_else(|| panic!("Parsed input is not from day {}", self.props.day))
+    }
 }
 
 impl<P> RunnableSolution for SolutionWrapper<P>
 where
-    P: PuzzleSolution,
+    P: ParsedPuzzleSolution + PuzzleParams,
 {
     fn get_puzzle(&self) -> Puzzle {
         Puzzle::new(self.props.day, self.props.year)
     }
 
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
-        self.solution.part1(puzzle)
+    fn parse(&self, puzzle: &Puzzle) -> Result<ParsedInput, String> {
+        match self.solution.parse(puzzle) {
+            Ok(parsed) => Ok(Box::new(parsed)),
+            Err(e) => Err(e.to_string()),
+        }
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
-        self.solution.part2(puzzle)
+    fn part1(&self, parsed: &ParsedInput) -> Answer {
+        self.solution.part1(self.downcast(parsed))
+    }
+
+    fn part2(&self, parsed: &ParsedInput) -> Answer {
+        self.solution.part2(self.downcast(parsed))
+    }
+
+    fn solve_both(&self, parsed: &ParsedInput) -> Option<(Answer, Answer)> {
+        self.solution.solve_both(self.downcast(parsed))
     }
 
     fn get_day(&self) -> u32 {
         self.props.day
     }
+
+    fn params(&self) -> Vec<Param> {
+        self.solution.params()
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        self.solution.set_param(name, value)
+    }
 }
@@ -1,3 +1,19 @@
+//! Timing of puzzle solutions
+//!
+//! [`timed!`](crate::timed) measures a single expression, [`span!`](crate::span)
+//! names a part of the code for the tree built by [`profiled`], [`TimingStats`]
+//! summarizes repeated runs and a [`Budget`] limits the time of the runner.
+
+mod allocator;
+mod budget;
+mod profiler;
+mod stats;
+
+pub use allocator::*;
+pub use budget::*;
+pub use profiler::*;
+pub use stats::*;
+
 #[macro_export]
 macro_rules! timed {
     ($x: expr) => {{
@@ -18,3 +34,30 @@ macro_rules! print_timed {
         result
     }};
 }
+
+/// Time a named part of the code while [`profiled`](crate::timer::profiled) runs
+///
+/// `span!("name")` returns a guard which ends the span when dropped,
+/// `span!("name", expr)` times only the expression.
+///
+/// ```
+/// use aoc_core::{span, timer::profiled};
+///
+/// let (sum, tree) = profiled("part1", || {
+///     let _span = span!("sum");
+///     let numbers = span!("parse", vec![1, 2, 3]);
+///     numbers.iter().sum::<i32>()
+/// });
+/// assert_eq!(sum, 6);
+/// assert_eq!(tree.get(&["sum", "parse"]).unwrap().calls, 1);
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name: expr) => {
+        $crate::timer::Span::enter($name)
+    };
+    ($name: expr, $x: expr) => {{
+        let _span = $crate::timer::Span::enter($name);
+        $x
+    }};
+}
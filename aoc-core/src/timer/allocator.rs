@@ -0,0 +1,166 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// System allocator which counts allocations, installed by the `alloc-stats` feature
+///
+/// The counts are for the whole process, allocations of other threads are
+/// included in the spans.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Memory allocated in a span
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Total bytes allocated, including memory which was freed again
+    pub bytes: usize,
+    pub count: usize,
+    /// Most memory in use at once, above what was in use when the span started
+    pub peak: usize,
+}
+
+impl Allocations {
+    pub(crate) fn merge(self, other: Self) -> Self {
+        Self {
+            bytes: self.bytes + other.bytes,
+            count: self.count + other.count,
+            peak: self.peak.max(other.peak),
+        }
+    }
+}
+
+impl Display for Allocations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in {} allocs, peak {}",
+            Bytes(self.bytes),
+            self.count,
+            Bytes(self.peak)
+        )
+    }
+}
+
+struct Bytes(usize);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{} B", self.0),
+            _ => write!(f, "{:.1} {}", value, UNITS[unit]),
+        }
+    }
+}
+
+/// Counters when a span started, the peak is reset so it can be measured per span
+#[derive(Default)]
+pub(crate) struct Snapshot(Option<(usize, usize, usize, usize)>);
+
+impl Snapshot {
+    pub(crate) fn take() -> Self {
+        if !cfg!(feature = "alloc-stats") {
+            return Self(None);
+        }
+        let current = CURRENT.load(Ordering::Relaxed);
+        let peak = PEAK.swap(current, Ordering::Relaxed);
+        Self(Some((
+            ALLOCATED.load(Ordering::Relaxed),
+            COUNT.load(Ordering::Relaxed),
+            current,
+            peak,
+        )))
+    }
+
+    /// Allocations since the snapshot was taken, restores the peak of the outer span
+    pub(crate) fn since(self) -> Option<Allocations> {
+        let (allocated, count, current, outer_peak) = self.0?;
+        let peak = PEAK.fetch_max(outer_peak, Ordering::Relaxed);
+        Some(Allocations {
+            bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+            count: COUNT.load(Ordering::Relaxed) - count,
+            peak: peak.saturating_sub(current),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let allocations = Allocations {
+            bytes: 3 * 1024 * 1024 / 2,
+            count: 12,
+            peak: 100,
+        };
+        assert_eq!(allocations.to_string(), "1.5 MiB in 12 allocs, peak 100 B");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn counts_allocations() {
+        let snapshot = Snapshot::take();
+        let data = vec![0u8; 4096];
+        drop(data);
+        let allocations = snapshot.since().unwrap();
+        assert!(allocations.count >= 1);
+        assert!(allocations.bytes >= 4096);
+    }
+}
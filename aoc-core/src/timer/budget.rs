@@ -0,0 +1,308 @@
+use std::{collections::HashMap, fmt::Display, path::Path, time::Duration};
+
+/// Times of a day as reported by the runner, the medians when repeating
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayTimes {
+    pub day: u32,
+    pub parse: Duration,
+    /// `part1` and `part2`, or `1+2` when both were solved in one pass
+    pub parts: Vec<(&'static str, Duration)>,
+}
+
+impl DayTimes {
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|(_, time)| *time).sum::<Duration>()
+    }
+}
+
+/// Time limits of the runner, read from a TOML file or set on the command line
+///
+/// ```toml
+/// total = "1s"
+/// day = "100ms"
+/// part = "50ms"
+///
+/// [days]
+/// 6 = "400ms"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// Limit of all days together
+    pub total: Option<Duration>,
+    /// Limit of every day without a limit of its own
+    pub day: Option<Duration>,
+    pub days: HashMap<u32, Duration>,
+    /// Limit of every part, or of both parts when solved in one pass
+    pub part: Option<Duration>,
+}
+
+impl Budget {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_toml(&toml).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, String> {
+        let table = toml::from_str::<toml::Table>(toml).map_err(|e| e.message().to_string())?;
+        let mut budget = Self::default();
+        for (key, value) in &table {
+            match key.as_str() {
+                "total" => budget.total = Some(duration_value(key, value)?),
+                "day" => budget.day = Some(duration_value(key, value)?),
+                "part" => budget.part = Some(duration_value(key, value)?),
+                "days" => {
+                    let days = value
+                        .as_table()
+                        .ok_or("`days` should be a table, like `[days]`")?;
+                    for (day, value) in days {
+                        budget
+                            .days
+                            .insert(parse_day(day)?, duration_value(day, value)?);
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown budget '{}', expected total, day, part or days",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(budget)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total.is_none() && self.day.is_none() && self.days.is_empty() && self.part.is_none()
+    }
+
+    /// Limit of the day, its own or the one of every day
+    pub fn of_day(&self, day: u32) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.day)
+    }
+
+    pub fn report<'a>(&'a self, days: &'a [DayTimes]) -> BudgetReport<'a> {
+        let mut ranked = days.iter().collect::<Vec<_>>();
+        ranked.sort_by(|a, b| b.total().cmp(&a.total()).then(a.day.cmp(&b.day)));
+        BudgetReport {
+            budget: self,
+            days: ranked,
+        }
+    }
+}
+
+/// Parse a duration like `1s`, `1.5ms`, `250us` or `100ns`
+pub fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let duration = duration.trim();
+    let split = duration
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(duration.len());
+    let (value, unit) = duration.split_at(split);
+    let invalid = || format!("Expected a duration like 100ms, got '{}'", duration);
+    let seconds = match unit.trim() {
+        "s" => 1.0,
+        "ms" => 1e-3,
+        "us" | "µs" => 1e-6,
+        "ns" => 1e-9,
+        _ => return Err(invalid()),
+    };
+    let value = value.parse::<f64>().map_err(|_| invalid())?;
+    Duration::try_from_secs_f64(value * seconds).map_err(|_| invalid())
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    day.trim_start_matches("day")
+        .parse()
+        .map_err(|_| format!("Expected a day number in `days`, got '{}'", day))
+}
+
+fn duration_value(key: &str, value: &toml::Value) -> Result<Duration, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("Expected a duration like \"100ms\" for `{}`", key))
+        .and_then(parse_duration)
+        .map_err(|e| format!("{}: {}", key, e))
+}
+
+/// Days ranked from slow to fast, with everything that took longer than its budget
+pub struct BudgetReport<'a> {
+    budget: &'a Budget,
+    days: Vec<&'a DayTimes>,
+}
+
+/// A day, part or the total which took longer than its budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overrun {
+    pub name: String,
+    pub time: Duration,
+    pub budget: Duration,
+}
+
+impl BudgetReport<'_> {
+    pub fn total(&self) -> Duration {
+        self.days.iter().map(|day| day.total()).sum()
+    }
+
+    pub fn overruns(&self) -> Vec<Overrun> {
+        let mut overruns = vec![];
+        let mut check = |name: String, time: Duration, budget: Option<Duration>| {
+            if let Some(budget) = budget.filter(|budget| time > *budget) {
+                overruns.push(Overrun { name, time, budget });
+            }
+        };
+        check("total".to_string(), self.total(), self.budget.total);
+        for day in &self.days {
+            check(
+                format!("day {}", day.day),
+                day.total(),
+                self.budget.of_day(day.day),
+            );
+            for (part, time) in &day.parts {
+                check(format!("day {} {}", day.day, part), *time, self.budget.part);
+            }
+        }
+        overruns
+    }
+
+    pub fn passed(&self) -> bool {
+        self.overruns().is_empty()
+    }
+}
+
+impl Display for BudgetReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = self.total();
+        writeln!(f, "Budget report")?;
+        writeln!(
+            f,
+            "{:>4}  {:<6} {:>10} {:>7} {:>10}",
+            "rank", "day", "time", "share", "budget"
+        )?;
+        for (rank, day) in self.days.iter().enumerate() {
+            let share = match total.is_zero() {
+                true => 0.0,
+                false => day.total().as_secs_f64() / total.as_secs_f64() * 100.0,
+            };
+            let budget = match self.budget.of_day(day.day) {
+                Some(budget) => format!("{:.2?}", budget),
+                None => "-".to_string(),
+            };
+            writeln!(
+                f,
+                "{:>4}  {:<6} {:>10.2?} {:>6.1}% {:>10}",
+                rank + 1,
+                format!("day {}", day.day),
+                day.total(),
+                share,
+                budget
+            )?;
+        }
+        match self.budget.total {
+            Some(budget) => writeln!(f, "total: {:.2?} of {:.2?}", total, budget)?,
+            None => writeln!(f, "total: {:.2?}", total)?,
+        }
+        let overruns = self.overruns();
+        if !overruns.is_empty() {
+            writeln!(f, "Over budget:")?;
+            for overrun in overruns {
+                writeln!(f, "  {}", overrun)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Overrun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:.2?}, {:.2?} over its budget of {:.2?}",
+            self.name,
+            self.time,
+            self.time - self.budget,
+            self.budget
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn day(day: u32, parse: u64, parts: &[(&'static str, u64)]) -> DayTimes {
+        DayTimes {
+            day,
+            parse: ms(parse),
+            parts: parts
+                .iter()
+                .map(|(name, time)| (*name, ms(*time)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("1s"), Ok(ms(1000)));
+        assert_eq!(parse_duration("1.5ms"), Ok(Duration::from_micros(1500)));
+        assert_eq!(parse_duration("250us"), Ok(Duration::from_micros(250)));
+        assert_eq!(parse_duration("250µs"), Ok(Duration::from_micros(250)));
+        assert_eq!(parse_duration(" 100 ns"), Ok(Duration::from_nanos(100)));
+        assert!(parse_duration("100").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("1h").is_err());
+        assert!(parse_duration("99999999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn from_toml() {
+        let budget = Budget::from_toml(
+            "total = \"1s\"\nday = \"100ms\"\n\n[days]\n6 = \"400ms\"\nday20 = \"200ms\"\n",
+        )
+        .unwrap();
+        assert_eq!(budget.total, Some(ms(1000)));
+        assert_eq!(budget.part, None);
+        assert_eq!(budget.of_day(6), Some(ms(400)));
+        assert_eq!(budget.of_day(20), Some(ms(200)));
+        assert_eq!(budget.of_day(1), Some(ms(100)));
+        assert!(Budget::default().is_empty());
+        assert!(Budget::from_toml("total = 1").is_err());
+        assert!(Budget::from_toml("days = \"1s\"").is_err());
+        assert!(Budget::from_toml("[days]\nsix = \"1s\"").is_err());
+        assert!(Budget::from_toml("totl = \"1s\"")
+            .unwrap_err()
+            .contains("Unknown budget 'totl'"));
+    }
+
+    #[test]
+    fn report() {
+        let days = [
+            day(1, 1, &[("part1", 2), ("part2", 3)]),
+            day(6, 10, &[("part1", 50), ("part2", 300)]),
+            day(10, 5, &[("1+2", 20)]),
+        ];
+        let budget = Budget {
+            total: Some(ms(300)),
+            day: Some(ms(100)),
+            part: Some(ms(250)),
+            ..Default::default()
+        };
+        let report = budget.report(&days);
+        assert_eq!(report.total(), ms(391));
+        assert_eq!(
+            report.days.iter().map(|day| day.day).collect::<Vec<_>>(),
+            vec![6, 10, 1]
+        );
+        let overruns = report
+            .overruns()
+            .into_iter()
+            .map(|overrun| overrun.name)
+            .collect::<Vec<_>>();
+        assert_eq!(overruns, vec!["total", "day 6", "day 6 part2"]);
+        assert!(!report.passed());
+        assert!(Budget::default().report(&days).passed());
+    }
+}
@@ -0,0 +1,281 @@
+use std::{cell::RefCell, fmt::Display, time::Duration, time::Instant};
+
+use super::allocator::{self, Allocations};
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Time spent in a named part of the code, see [`span!`](crate::span)
+///
+/// Spans are only recorded inside [`profiled`] and on the same thread, otherwise
+/// they do nothing.
+#[must_use = "the span ends when it is dropped"]
+pub struct Span {
+    active: bool,
+}
+
+impl Span {
+    pub fn enter(name: &'static str) -> Self {
+        let active = RECORDER.with(|recorder| match recorder.borrow_mut().as_mut() {
+            Some(recorder) => {
+                recorder.enter(name);
+                true
+            }
+            None => false,
+        });
+        Self { active }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if self.active {
+            RECORDER.with(|recorder| {
+                if let Some(recorder) = recorder.borrow_mut().as_mut() {
+                    recorder.exit();
+                }
+            });
+        }
+    }
+}
+
+/// Run `f` and record the spans entered while it runs in a tree with `name` as root
+pub fn profiled<R>(name: &'static str, f: impl FnOnce() -> R) -> (R, ProfileTree) {
+    let previous = RECORDER.with(|recorder| recorder.replace(Some(Recorder::new(name))));
+    let result = f();
+    let mut recorder = RECORDER
+        .with(|recorder| recorder.replace(previous))
+        .expect("Profile recorder");
+    recorder.exit();
+    (result, recorder.tree)
+}
+
+/// Timing tree of a [`profiled`] run, spans with the same name and parent are merged
+#[derive(Debug, Clone)]
+pub struct ProfileTree {
+    nodes: Vec<ProfileNode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfileNode {
+    pub name: &'static str,
+    /// Times the span was entered, including recursive calls
+    pub calls: usize,
+    pub total: Duration,
+    /// Allocations made in the span, when the `alloc-stats` feature is enabled
+    pub allocations: Option<Allocations>,
+    children: Vec<usize>,
+}
+
+impl ProfileTree {
+    pub fn root(&self) -> &ProfileNode {
+        &self.nodes[0]
+    }
+
+    pub fn children<'a>(&'a self, node: &'a ProfileNode) -> impl Iterator<Item = &'a ProfileNode> {
+        node.children.iter().map(|&i| &self.nodes[i])
+    }
+
+    /// Node at a path of span names below the root
+    pub fn get(&self, path: &[&str]) -> Option<&ProfileNode> {
+        path.iter().try_fold(self.root(), |node, name| {
+            self.children(node).find(|child| child.name == *name)
+        })
+    }
+
+    fn write_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        node: &ProfileNode,
+        prefix: &str,
+        last: bool,
+    ) -> std::fmt::Result {
+        let is_root = std::ptr::eq(node, self.root());
+        let (branch, indent) = match (is_root, last) {
+            (true, _) => ("", ""),
+            (false, true) => ("└─ ", "   "),
+            (false, false) => ("├─ ", "│  "),
+        };
+        let root = self.root().total.as_secs_f64();
+        let share = match root > 0.0 {
+            true => node.total.as_secs_f64() / root * 100.0,
+            false => 100.0,
+        };
+        let label = format!("{}{}{}", prefix, branch, node.name);
+        write!(
+            f,
+            "{:<40} {:>10.2?} {:>6.1}% {:>8} calls",
+            label, node.total, share, node.calls
+        )?;
+        if let Some(allocations) = &node.allocations {
+            write!(f, "  {}", allocations)?;
+        }
+        writeln!(f)?;
+        let prefix = format!("{}{}", prefix, indent);
+        let children = self.children(node).collect::<Vec<_>>();
+        for (i, child) in children.iter().enumerate() {
+            self.write_node(f, child, &prefix, i == children.len() - 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ProfileTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_node(f, self.root(), "", true)
+    }
+}
+
+struct Recorder {
+    tree: ProfileTree,
+    stack: Vec<Frame>,
+}
+
+struct Frame {
+    node: usize,
+    start: Instant,
+    allocations: allocator::Snapshot,
+    /// Direct recursion of a span is merged into the outer call, which has the time
+    recursive: bool,
+}
+
+impl Recorder {
+    fn new(name: &'static str) -> Self {
+        let mut recorder = Self {
+            tree: ProfileTree { nodes: vec![] },
+            stack: vec![],
+        };
+        recorder.push(name, None);
+        recorder
+    }
+
+    fn enter(&mut self, name: &'static str) {
+        let parent = self.stack.last().map(|frame| frame.node);
+        match parent {
+            Some(parent) if self.tree.nodes[parent].name == name => {
+                self.tree.nodes[parent].calls += 1;
+                self.stack.push(Frame {
+                    node: parent,
+                    start: Instant::now(),
+                    allocations: allocator::Snapshot::default(),
+                    recursive: true,
+                });
+            }
+            _ => self.push(name, parent),
+        }
+    }
+
+    fn push(&mut self, name: &'static str, parent: Option<usize>) {
+        let existing = parent.and_then(|parent| {
+            self.tree.nodes[parent]
+                .children
+                .iter()
+                .copied()
+                .find(|&child| self.tree.nodes[child].name == name)
+        });
+        let node = existing.unwrap_or_else(|| {
+            self.tree.nodes.push(ProfileNode {
+                name,
+                calls: 0,
+                total: Duration::default(),
+                allocations: None,
+                children: vec![],
+            });
+            let node = self.tree.nodes.len() - 1;
+            if let Some(parent) = parent {
+                self.tree.nodes[parent].children.push(node);
+            }
+            node
+        });
+        self.tree.nodes[node].calls += 1;
+        self.stack.push(Frame {
+            node,
+            allocations: allocator::Snapshot::take(),
+            start: Instant::now(),
+            recursive: false,
+        });
+    }
+
+    fn exit(&mut self) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        if frame.recursive {
+            return;
+        }
+        let elapsed = frame.start.elapsed();
+        let node = &mut self.tree.nodes[frame.node];
+        node.total += elapsed;
+        if let Some(allocations) = frame.allocations.since() {
+            node.allocations = Some(match node.allocations {
+                Some(previous) => previous.merge(allocations),
+                None => allocations,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64) -> u64 {
+        let _span = crate::span!("fib");
+        match n {
+            0 | 1 => n,
+            n => fib(n - 1) + fib(n - 2),
+        }
+    }
+
+    #[test]
+    fn builds_tree() {
+        let (result, tree) = profiled("part1", || {
+            let parsed = crate::span!("parse", vec![1, 2, 3]);
+            for _ in 0..2 {
+                let _span = crate::span!("solve");
+                crate::span!("inner", ());
+            }
+            parsed.len()
+        });
+        assert_eq!(result, 3);
+        assert_eq!(tree.root().name, "part1");
+        assert_eq!(tree.root().calls, 1);
+        assert_eq!(tree.get(&["parse"]).unwrap().calls, 1);
+        assert_eq!(tree.get(&["solve"]).unwrap().calls, 2);
+        assert_eq!(tree.get(&["solve", "inner"]).unwrap().calls, 2);
+        assert!(tree.get(&["inner"]).is_none());
+        assert!(tree.root().total >= tree.get(&["solve"]).unwrap().total);
+    }
+
+    #[test]
+    fn merges_recursion() {
+        let (result, tree) = profiled("part2", || fib(10));
+        assert_eq!(result, 55);
+        let fib = tree.get(&["fib"]).unwrap();
+        assert_eq!(fib.calls, 177);
+        assert_eq!(tree.children(fib).count(), 0);
+        assert!(fib.total <= tree.root().total);
+    }
+
+    #[test]
+    fn inactive_outside_profile() {
+        assert_eq!(fib(5), 5);
+        let (_, tree) = profiled("part1", || ());
+        assert!(tree.get(&["fib"]).is_none());
+    }
+
+    #[test]
+    fn display() {
+        let (_, tree) = profiled("part1", || {
+            crate::span!("a", crate::span!("b", ()));
+            crate::span!("c", ());
+        });
+        let lines = tree
+            .to_string()
+            .lines()
+            .map(|line| line.split_whitespace().next().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["part1", "├─", "│", "└─"]);
+    }
+}
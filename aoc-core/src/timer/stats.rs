@@ -0,0 +1,123 @@
+use std::{fmt::Display, time::Duration};
+
+/// Statistics of the times of repeated runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingStats {
+    /// Sorted, never empty
+    samples: Vec<Duration>,
+}
+
+impl TimingStats {
+    /// Returns `None` without samples
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        Some(Self { samples })
+    }
+
+    pub fn runs(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+
+    pub fn median(&self) -> Duration {
+        let n = self.samples.len();
+        match n % 2 {
+            0 => (self.samples[n / 2 - 1] + self.samples[n / 2]) / 2,
+            _ => self.samples[n / 2],
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    /// Nearest-rank percentile, `percentile(95.0)` is slower than 95% of the runs
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        let rank = (percentile / 100.0 * self.samples.len() as f64).ceil() as usize;
+        self.samples[rank.clamp(1, self.samples.len()) - 1]
+    }
+
+    pub fn p95(&self) -> Duration {
+        self.percentile(95.0)
+    }
+
+    /// Sample standard deviation
+    pub fn stddev(&self) -> Duration {
+        let n = self.samples.len();
+        if n < 2 {
+            return Duration::ZERO;
+        }
+        let mean = self.mean().as_secs_f64();
+        let variance = self
+            .samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+}
+
+impl Display for TimingStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.2?}, median {:.2?}, mean {:.2?}, p95 {:.2?}, stddev {:.2?} ({} runs)",
+            self.min(),
+            self.median(),
+            self.mean(),
+            self.p95(),
+            self.stddev(),
+            self.runs()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> TimingStats {
+        TimingStats::new(values.iter().map(|&ms| Duration::from_millis(ms)).collect()).unwrap()
+    }
+
+    #[test]
+    fn statistics() {
+        let stats = millis(&[5, 1, 3, 2, 4]);
+        assert_eq!(stats.runs(), 5);
+        assert_eq!(stats.min(), Duration::from_millis(1));
+        assert_eq!(stats.max(), Duration::from_millis(5));
+        assert_eq!(stats.median(), Duration::from_millis(3));
+        assert_eq!(stats.mean(), Duration::from_millis(3));
+        assert_eq!(stats.p95(), Duration::from_millis(5));
+        assert_eq!(stats.percentile(40.0), Duration::from_millis(2));
+        assert_eq!(stats.stddev().as_micros(), 1581);
+    }
+
+    #[test]
+    fn even_and_single() {
+        assert_eq!(millis(&[4, 1, 2, 3]).median(), Duration::from_micros(2500));
+        let single = millis(&[7]);
+        assert_eq!(single.p95(), Duration::from_millis(7));
+        assert_eq!(single.stddev(), Duration::ZERO);
+        assert!(TimingStats::new(vec![]).is_none());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            millis(&[1, 1]).to_string(),
+            "min 1.00ms, median 1.00ms, mean 1.00ms, p95 1.00ms, stddev 0.00ns (2 runs)"
+        );
+    }
+}
@@ -1,6 +1,21 @@
+mod astar;
+pub use astar::*;
 mod bfs;
 pub use bfs::*;
+mod cycle;
+pub use cycle::*;
 mod dijkstra;
 pub use dijkstra::*;
+mod disjoint_set;
+pub use disjoint_set::*;
 mod grid;
+pub use aoc_procmacro::GridCell;
 pub use grid::*;
+mod incremental;
+pub use incremental::*;
+mod memo;
+pub use memo::*;
+mod range_set;
+pub use range_set::*;
+pub mod math;
+pub mod parse;
@@ -0,0 +1,297 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use super::CostInput;
+
+type Coord = (usize, usize);
+type HeuristicFn = dyn Fn(Coord, Coord) -> usize;
+
+/// Estimate of the remaining cost from a cell to the end of the search
+///
+/// The estimate should never exceed the real remaining cost, otherwise the
+/// found path is not guaranteed to be the shortest.
+pub enum Heuristic {
+    /// No estimate, A* will behave like Dijkstra
+    Zero,
+    /// Sum of the horizontal and vertical distance, best fit for 4-way movement
+    Manhattan,
+    /// Largest of the horizontal and vertical distance, best fit for 8-way movement
+    Chebyshev,
+    /// Custom estimate, called with the current cell and the end cell
+    Custom(Box<HeuristicFn>),
+}
+
+impl Heuristic {
+    pub fn estimate(&self, from: Coord, to: Coord) -> usize {
+        match self {
+            Heuristic::Zero => 0,
+            Heuristic::Manhattan => from.0.abs_diff(to.0) + from.1.abs_diff(to.1),
+            Heuristic::Chebyshev => from.0.abs_diff(to.0).max(from.1.abs_diff(to.1)),
+            Heuristic::Custom(func) => func(from, to),
+        }
+    }
+}
+
+/// A builder for the A* algorithm
+///
+/// Without a custom cost function every step costs 1 and the default
+/// Manhattan heuristic is admissible.
+pub struct AStarBuilder {
+    obstacles: HashSet<Coord>,
+    start: Coord,
+    end: Coord,
+    bounds: Option<(Coord, Coord)>,
+    calculate_cost: Box<dyn Fn(CostInput) -> usize>,
+    heuristic: Heuristic,
+}
+
+impl AStarBuilder {
+    pub fn new(start: Coord, end: Coord) -> Self {
+        Self {
+            start,
+            end,
+            obstacles: HashSet::new(),
+            bounds: None,
+            calculate_cost: Box::new(|input| input.cost + 1),
+            heuristic: Heuristic::Manhattan,
+        }
+    }
+
+    /// Add obstacles to the grid, this can be called multiple times
+    pub fn with_obstacles(mut self, obstacles: HashSet<Coord>) -> Self {
+        self.obstacles.extend(obstacles);
+        self
+    }
+
+    /// Enforce search bounds, if not provided the bounds will be calculated
+    /// based on the start, end and obstacles
+    pub fn with_bounds(mut self, top_left: Coord, bottom_right: Coord) -> Self {
+        self.bounds = Some((top_left, bottom_right));
+        self
+    }
+
+    /// Calculate the total cost of moving to the next cell, defaults to the
+    /// current cost + 1
+    pub fn with_cost_func<F>(mut self, func: F) -> Self
+    where
+        F: Fn(CostInput) -> usize + 'static,
+    {
+        self.calculate_cost = Box::new(func);
+        self
+    }
+
+    /// Use a different heuristic, defaults to [`Heuristic::Manhattan`]
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Use a custom heuristic, called with the current cell and the end cell
+    pub fn with_heuristic_fn<F>(self, func: F) -> Self
+    where
+        F: Fn(Coord, Coord) -> usize + 'static,
+    {
+        self.with_heuristic(Heuristic::Custom(Box::new(func)))
+    }
+
+    /// Run the A* algorithm
+    pub fn run(mut self) -> AStarResult {
+        self.determine_bounds();
+        let end = self.end;
+        // Ordered by lowest estimate first, ties prefer the cell furthest along the path
+        let mut queue = BinaryHeap::from([Reverse((
+            self.heuristic.estimate(self.start, end),
+            Reverse(0),
+            self.start,
+        ))]);
+        let mut dist_map = HashMap::from([(self.start, (0, None))]);
+        let mut expanded = 0;
+        while let Some(Reverse((_, Reverse(cost), (x, y)))) = queue.pop() {
+            if dist_map.get(&(x, y)).is_some_and(|(c, _)| *c < cost) {
+                // Stale queue entry, a cheaper route to this cell was found
+                continue;
+            }
+            expanded += 1;
+            if (x, y) == end {
+                break;
+            }
+            for (nx, ny) in self.visitable_neighbors(x, y) {
+                let next_cost = (self.calculate_cost)(CostInput {
+                    origin: (x, y),
+                    next: (nx, ny),
+                    cost,
+                });
+                if let Some((existing_cost, _)) = dist_map.get(&(nx, ny)) {
+                    if *existing_cost <= next_cost {
+                        continue;
+                    }
+                }
+                dist_map.insert((nx, ny), (next_cost, Some((x, y))));
+                let estimate = next_cost + self.heuristic.estimate((nx, ny), end);
+                queue.push(Reverse((estimate, Reverse(next_cost), (nx, ny))));
+            }
+        }
+        AStarResult {
+            map: dist_map,
+            end,
+            expanded,
+        }
+    }
+
+    // Retrieve the neighbors of a cell that are not obstacles and are within the bounds of the grid
+    fn visitable_neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = Coord> + '_ {
+        let (top_left, bottom_right) = self.bounds.unwrap();
+        [
+            (x.checked_sub(1), Some(y)),
+            (Some(x + 1), Some(y)),
+            (Some(x), y.checked_sub(1)),
+            (Some(x), Some(y + 1)),
+        ]
+        .into_iter()
+        .filter_map(|(nx, ny)| Some((nx?, ny?)))
+        .filter(move |(nx, ny)| {
+            *nx >= top_left.0 && *nx <= bottom_right.0 && *ny >= top_left.1 && *ny <= bottom_right.1
+        })
+        .filter(|(nx, ny)| !self.obstacles.contains(&(*nx, *ny)))
+    }
+
+    // Determine grid bounds if not defined
+    fn determine_bounds(&mut self) {
+        if self.bounds.is_some() {
+            return;
+        }
+        let mut top_left = min_coord(self.start, self.end);
+        let mut bottom_right = max_coord(self.start, self.end);
+        for c in self.obstacles.iter() {
+            top_left = min_coord(top_left, *c);
+            bottom_right = max_coord(bottom_right, *c);
+        }
+        self.bounds = Some((top_left, bottom_right));
+    }
+}
+
+fn min_coord(a: Coord, b: Coord) -> Coord {
+    (a.0.min(b.0), a.1.min(b.1))
+}
+fn max_coord(a: Coord, b: Coord) -> Coord {
+    (a.0.max(b.0), a.1.max(b.1))
+}
+
+/// Result of the A* algorithm
+pub struct AStarResult {
+    map: HashMap<Coord, (usize, Option<Coord>)>,
+    end: Coord,
+    expanded: usize,
+}
+
+impl AStarResult {
+    pub fn found_path(&self) -> bool {
+        self.map.contains_key(&self.end)
+    }
+
+    /// Cost of the path to the end, if any
+    pub fn cost(&self) -> Option<usize> {
+        self.map.get(&self.end).map(|(cost, _)| *cost)
+    }
+
+    /// Amount of cells taken from the queue and expanded during the search
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    pub fn path(&self) -> Option<Vec<Coord>> {
+        self.map.get(&self.end).map(|v| {
+            let mut path = vec![self.end];
+            let mut next = v.1;
+            while let Some(cur) = next {
+                path.push(cur);
+                next = self.map[&cur].1;
+            }
+            path.reverse();
+            path
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Test case:
+    S..###
+    .#....
+    .#.###
+    .#.#.E
+    .#...#
+    */
+    fn create_with_testcase() -> AStarBuilder {
+        AStarBuilder::new((0, 0), (5, 3)).with_obstacles(HashSet::from([
+            (3, 0),
+            (4, 0),
+            (5, 0),
+            (1, 1),
+            (1, 2),
+            (3, 2),
+            (4, 2),
+            (5, 2),
+            (1, 3),
+            (3, 3),
+            (1, 4),
+            (5, 4),
+        ]))
+    }
+
+    #[test]
+    fn test_path_finding() {
+        let result = create_with_testcase().run();
+        assert_eq!(result.cost(), Some(10));
+        assert_eq!(
+            result.path().expect("Path found"),
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+                (4, 4),
+                (4, 3),
+                (5, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_path() {
+        let result = create_with_testcase()
+            .with_obstacles(HashSet::from([(2, 1)]))
+            .run();
+        assert!(!result.found_path());
+        assert_eq!(result.path(), None);
+    }
+
+    #[test]
+    fn test_heuristics_expand_less() {
+        let open_field = || AStarBuilder::new((0, 0), (20, 20)).with_bounds((0, 0), (20, 20));
+        let zero = open_field().with_heuristic(Heuristic::Zero).run();
+        let chebyshev = open_field().with_heuristic(Heuristic::Chebyshev).run();
+        let manhattan = open_field().with_heuristic(Heuristic::Manhattan).run();
+        assert_eq!(zero.cost(), Some(40));
+        assert_eq!(chebyshev.cost(), Some(40));
+        assert_eq!(manhattan.cost(), Some(40));
+        assert!(manhattan.expanded() < chebyshev.expanded());
+        assert!(chebyshev.expanded() < zero.expanded());
+    }
+
+    #[test]
+    fn test_custom_heuristic() {
+        let result = create_with_testcase()
+            .with_heuristic_fn(|(x, y), (ex, ey)| x.abs_diff(ex) + y.abs_diff(ey))
+            .run();
+        assert_eq!(result.cost(), Some(10));
+    }
+}
@@ -53,12 +53,14 @@ impl BfsBuilder {
     pub fn run(mut self) -> Option<BfsResult> {
         let mut queue = VecDeque::from([self.start]);
         let mut visited = HashMap::from([(self.start, None)]);
+        let mut expanded = 0;
 
         while let Some((x, y)) = if self.use_dfs {
             queue.pop_back()
         } else {
             queue.pop_front()
         } {
+            expanded += 1;
             if x == self.end.0 && y == self.end.1 {
                 let mut path = vec![(x, y)];
                 let mut cur = (x, y);
@@ -67,7 +69,7 @@ impl BfsBuilder {
                     cur = *prev;
                 }
                 path.reverse();
-                return Some(BfsResult { path });
+                return Some(BfsResult { path, expanded });
             }
             for (nx, ny) in self.visitable_neighbors(x, y) {
                 if visited.contains_key(&(nx, ny)) {
@@ -125,6 +127,7 @@ fn max_coord(a: Coord, b: Coord) -> Coord {
 /// Result of the Breadth First Search algorithm
 pub struct BfsResult {
     path: Vec<Coord>,
+    expanded: usize,
 }
 impl BfsResult {
     pub fn path(&self) -> &Vec<Coord> {
@@ -136,6 +139,10 @@ impl BfsResult {
     pub fn is_empty(&self) -> bool {
         self.path.is_empty()
     }
+    /// Amount of cells taken from the queue and expanded during the search
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,254 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::math::crt;
+
+/// Position of a cycle in a sequence of states
+///
+/// The state after `mu + lambda` steps equals the state after `mu` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before the first state of the cycle
+    pub mu: usize,
+    /// Length of the cycle
+    pub lambda: usize,
+}
+
+impl Cycle {
+    /// Smallest step which results in the same state as step `n`
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+}
+
+/// Find the cycle in the states produced by `step` with Brent's algorithm,
+/// `None` when the sequence ends before repeating
+///
+/// Only a few states are kept in memory, but `step` is called multiple times
+/// for the same state so it must be deterministic.
+pub fn find_cycle<T, F>(initial: T, mut step: F) -> Option<Cycle>
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T) -> Option<T>,
+{
+    // Find the cycle length by moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare)?;
+        lambda += 1;
+    }
+
+    // Find the start of the cycle with the hare lambda steps ahead
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..lambda {
+        hare = step(&hare)?;
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        mu += 1;
+    }
+    Some(Cycle { mu, lambda })
+}
+
+/// Find the cycle in the states produced by `step` by remembering every state,
+/// `None` when the sequence ends before repeating
+///
+/// Uses more memory than [`find_cycle`], but calls `step` once per state.
+pub fn find_cycle_hashed<T, F>(initial: T, step: F) -> Option<Cycle>
+where
+    T: Clone + Hash + Eq,
+    F: FnMut(&T) -> Option<T>,
+{
+    let mut history = History::new(initial);
+    history.run_until(usize::MAX, step);
+    history.cycle
+}
+
+/// State after `n` steps, skipping ahead once the states repeat
+///
+/// Returns `None` when the sequence ends before `n` steps.
+pub fn advance_to<T, F>(initial: T, n: usize, step: F) -> Option<T>
+where
+    T: Clone + Hash + Eq,
+    F: FnMut(&T) -> Option<T>,
+{
+    let mut history = History::new(initial);
+    history.run_until(n, step);
+    let n = match history.cycle {
+        Some(cycle) => cycle.equivalent_step(n),
+        None => n,
+    };
+    history.states.get(n).cloned()
+}
+
+struct History<T> {
+    states: Vec<T>,
+    seen: HashMap<T, usize>,
+    cycle: Option<Cycle>,
+}
+
+impl<T> History<T>
+where
+    T: Clone + Hash + Eq,
+{
+    fn new(initial: T) -> Self {
+        Self {
+            seen: HashMap::from([(initial.clone(), 0)]),
+            states: vec![initial],
+            cycle: None,
+        }
+    }
+
+    // Record states until step `n`, a cycle is found or the sequence ends
+    fn run_until<F: FnMut(&T) -> Option<T>>(&mut self, n: usize, mut step: F) {
+        while self.states.len() <= n {
+            let next = match step(self.states.last().unwrap()) {
+                Some(next) => next,
+                None => return,
+            };
+            if let Some(mu) = self.seen.get(&next) {
+                self.cycle = Some(Cycle {
+                    mu: *mu,
+                    lambda: self.states.len() - mu,
+                });
+                return;
+            }
+            self.seen.insert(next.clone(), self.states.len());
+            self.states.push(next);
+        }
+    }
+}
+
+type ScoreFn = dyn Fn(usize) -> i64;
+
+/// A state made of independent components which each repeat with their own period,
+/// like the x and y coordinates of objects wrapping around a grid
+///
+/// Every component is searched on its own for its best phase, after which the
+/// phases are combined to the first step where all components are at their best.
+#[derive(Default)]
+pub struct PeriodicComponents {
+    phases: Vec<(usize, usize)>,
+    scored: Vec<(usize, Box<ScoreFn>)>,
+}
+
+impl PeriodicComponents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add a component which is in the wanted state at steps `phase + k * period`
+    pub fn with_phase(mut self, phase: usize, period: usize) -> Self {
+        self.phases.push((phase % period, period));
+        self
+    }
+    /// Add a component with the given period, it is in phase at the steps within
+    /// the first period with the lowest score
+    pub fn with_component<F>(mut self, period: usize, score: F) -> Self
+    where
+        F: Fn(usize) -> i64 + 'static,
+    {
+        self.scored.push((period, Box::new(score)));
+        self
+    }
+
+    /// First step at which every component is in phase, `None` when the
+    /// phases can never line up
+    pub fn first_step(&self) -> Option<usize> {
+        let mut combined = vec![(0_i64, 1_i64)];
+        for (phases, period) in self.candidates() {
+            combined = combined
+                .iter()
+                .flat_map(|(a, n)| {
+                    phases
+                        .iter()
+                        .filter_map(move |phase| crt(&[(*a, *n), (*phase as i64, period as i64)]))
+                })
+                .collect();
+        }
+        combined.into_iter().map(|(step, _)| step as usize).min()
+    }
+
+    // Possible phases of every component, scored components can have ties
+    fn candidates(&self) -> Vec<(Vec<usize>, usize)> {
+        let scored = self.scored.iter().map(|(period, score)| {
+            let scores = (0..*period).map(score).collect::<Vec<_>>();
+            let best = scores.iter().min();
+            let phases = (0..*period)
+                .filter(|step| Some(&scores[*step]) == best)
+                .collect();
+            (phases, *period)
+        });
+        self.phases
+            .iter()
+            .map(|(phase, period)| (vec![*phase], *period))
+            .chain(scored)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2
+    fn step(state: &usize) -> Option<usize> {
+        Some(if *state == 5 { 2 } else { state + 1 })
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Some(Cycle { mu: 2, lambda: 4 });
+        assert_eq!(find_cycle(0, step), expected);
+        assert_eq!(find_cycle_hashed(0, step), expected);
+        assert_eq!(find_cycle(3, step), Some(Cycle { mu: 0, lambda: 4 }));
+        assert_eq!(
+            find_cycle(7, |s| Some(*s)),
+            Some(Cycle { mu: 0, lambda: 1 })
+        );
+        assert_eq!(find_cycle(0, |s| (*s < 10).then_some(s + 1)), None);
+        assert_eq!(find_cycle_hashed(0, |s| (*s < 10).then_some(s + 1)), None);
+    }
+
+    #[test]
+    fn test_advance_to() {
+        assert_eq!(advance_to(0, 1, step), Some(1));
+        assert_eq!(advance_to(0, 6, step), Some(2));
+        assert_eq!(advance_to(0, 1_000_000_000_003, step), Some(3));
+        assert_eq!(advance_to(0, 5, |s| (*s < 10).then_some(s + 1)), Some(5));
+        assert_eq!(advance_to(0, 15, |s| (*s < 10).then_some(s + 1)), None);
+        assert_eq!(Cycle { mu: 2, lambda: 4 }.equivalent_step(11), 3);
+    }
+
+    #[test]
+    fn test_periodic_components() {
+        let components = PeriodicComponents::new()
+            .with_component(5, |t| (t as i64 - 3).abs())
+            .with_component(7, |t| if t == 4 { 0 } else { 1 });
+        assert_eq!(components.first_step(), Some(18));
+        // Ties keep every phase and the earliest combination wins
+        let components = PeriodicComponents::new()
+            .with_component(5, |t| if t == 1 || t == 3 { 0 } else { 1 })
+            .with_phase(0, 2);
+        assert_eq!(components.first_step(), Some(6));
+
+        let components = PeriodicComponents::new().with_phase(1, 4).with_phase(3, 6);
+        assert_eq!(components.first_step(), Some(9));
+        let components = PeriodicComponents::new().with_phase(0, 4).with_phase(1, 6);
+        assert_eq!(components.first_step(), None);
+        assert_eq!(PeriodicComponents::new().first_step(), Some(0));
+    }
+}
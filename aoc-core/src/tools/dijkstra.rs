@@ -55,10 +55,12 @@ impl DijkstraBuilder {
                 parent: None,
             },
         )]);
+        let mut expanded = 0;
         self.determine_bounds();
         let cost_func = &self.calculate_cost;
         let end = self.end;
         while let Some(((x, y), cost)) = queue.pop_front() {
+            expanded += 1;
             for (nx, ny) in self.visitable_neighbors(x, y) {
                 let next_cost = cost_func(CostInput {
                     origin: (x, y),
@@ -85,6 +87,7 @@ impl DijkstraBuilder {
         DijkstraResult {
             map: dist_map,
             end: self.end,
+            expanded,
         }
     }
 
@@ -143,6 +146,7 @@ struct DijkstraEntry {
 pub struct DijkstraResult {
     map: HashMap<(usize, usize), DijkstraEntry>,
     end: (usize, usize),
+    expanded: usize,
 }
 
 impl DijkstraResult {
@@ -150,6 +154,11 @@ impl DijkstraResult {
         self.map.contains_key(&self.end)
     }
 
+    /// Amount of cells taken from the queue and expanded during the search
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
     pub fn path(&self) -> Option<Vec<(usize, usize)>> {
         self.map.get(&self.end).map(|v| {
             let mut path = vec![self.end];
@@ -0,0 +1,369 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Union-find structure with union by rank and path compression
+///
+/// Items are added on first use, every new item starts in its own set.
+pub struct DisjointSet<T> {
+    index: HashMap<T, usize>,
+    items: Vec<T>,
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl<T> Default for DisjointSet<T> {
+    fn default() -> Self {
+        Self {
+            index: HashMap::new(),
+            items: Vec::new(),
+            parent: Vec::new(),
+            rank: Vec::new(),
+            size: Vec::new(),
+            components: 0,
+        }
+    }
+}
+
+impl<T> DisjointSet<T>
+where
+    T: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item in its own set, returns false if the item was already known
+    pub fn insert(&mut self, item: T) -> bool {
+        if self.index.contains_key(&item) {
+            return false;
+        }
+        self.index_or_insert(item);
+        true
+    }
+
+    /// Merge the sets of both items, returns false if they were already connected
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let a = self.index_or_insert(a);
+        let b = self.index_or_insert(b);
+        let (a, b) = (self.find_index(a), self.find_index(b));
+        if a == b {
+            return false;
+        }
+        let (parent, child) = if self.rank[a] < self.rank[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        if self.rank[parent] == self.rank[child] {
+            self.rank[parent] += 1;
+        }
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        self.components -= 1;
+        true
+    }
+
+    /// Representative item of the set containing the item
+    pub fn find(&mut self, item: &T) -> Option<&T> {
+        let i = *self.index.get(item)?;
+        let root = self.find_index(i);
+        Some(&self.items[root])
+    }
+
+    pub fn connected(&mut self, a: &T, b: &T) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(a), Some(b)) => {
+                let (a, b) = (*a, *b);
+                self.find_index(a) == self.find_index(b)
+            }
+            _ => false,
+        }
+    }
+
+    /// Size of the set containing the item, 0 when the item is unknown
+    pub fn component_size(&mut self, item: &T) -> usize {
+        match self.index.get(item) {
+            Some(i) => {
+                let root = self.find_index(*i);
+                self.size[root]
+            }
+            None => 0,
+        }
+    }
+
+    /// Amount of disjoint sets
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over all sets, ordered by the first inserted item of each set
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<&T>> {
+        let roots = (0..self.items.len())
+            .map(|i| self.find_index(i))
+            .collect::<Vec<_>>();
+        let mut groups: Vec<Vec<&T>> = Vec::new();
+        let mut group_of_root = HashMap::new();
+        for (i, root) in roots.into_iter().enumerate() {
+            let group = *group_of_root.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(&self.items[i]);
+        }
+        groups.into_iter()
+    }
+
+    fn index_or_insert(&mut self, item: T) -> usize {
+        if let Some(i) = self.index.get(&item) {
+            return *i;
+        }
+        let i = self.items.len();
+        self.index.insert(item.clone(), i);
+        self.items.push(item);
+        self.parent.push(i);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        i
+    }
+
+    fn find_index(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut cur = i;
+        while self.parent[cur] != root {
+            cur = std::mem::replace(&mut self.parent[cur], root);
+        }
+        root
+    }
+}
+
+impl<T> FromIterator<T> for DisjointSet<T>
+where
+    T: Hash + Eq + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|item| {
+            set.insert(item);
+        });
+        set
+    }
+}
+
+/// Union-find structure which can undo unions, used for offline connectivity
+/// queries where edges are added and removed in a stack-like order
+///
+/// Uses union by rank without path compression, so lookups stay O(log n) and
+/// every union can be reverted. Items added after a snapshot are kept as single
+/// item sets on rollback.
+pub struct RollbackDisjointSet<T> {
+    index: HashMap<T, usize>,
+    items: Vec<T>,
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+    history: Vec<(usize, usize, bool)>,
+}
+
+impl<T> Default for RollbackDisjointSet<T> {
+    fn default() -> Self {
+        Self {
+            index: HashMap::new(),
+            items: Vec::new(),
+            parent: Vec::new(),
+            rank: Vec::new(),
+            size: Vec::new(),
+            components: 0,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl<T> RollbackDisjointSet<T>
+where
+    T: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item in its own set, returns false if the item was already known
+    pub fn insert(&mut self, item: T) -> bool {
+        if self.index.contains_key(&item) {
+            return false;
+        }
+        self.index_or_insert(item);
+        true
+    }
+
+    /// Merge the sets of both items, returns false if they were already connected
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let a = self.index_or_insert(a);
+        let b = self.index_or_insert(b);
+        let (a, b) = (self.find_index(a), self.find_index(b));
+        if a == b {
+            return false;
+        }
+        let (parent, child) = if self.rank[a] < self.rank[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let rank_increased = self.rank[parent] == self.rank[child];
+        if rank_increased {
+            self.rank[parent] += 1;
+        }
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        self.components -= 1;
+        self.history.push((child, parent, rank_increased));
+        true
+    }
+
+    /// Representative item of the set containing the item
+    pub fn find(&self, item: &T) -> Option<&T> {
+        let i = *self.index.get(item)?;
+        Some(&self.items[self.find_index(i)])
+    }
+
+    pub fn connected(&self, a: &T, b: &T) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(a), Some(b)) => self.find_index(*a) == self.find_index(*b),
+            _ => false,
+        }
+    }
+
+    /// Size of the set containing the item, 0 when the item is unknown
+    pub fn component_size(&self, item: &T) -> usize {
+        self.index
+            .get(item)
+            .map_or(0, |i| self.size[self.find_index(*i)])
+    }
+
+    /// Amount of disjoint sets
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Marker of the current state, pass it to `rollback` to undo later unions
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undo all unions done after the snapshot was taken
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            let (child, parent, rank_increased) = self.history.pop().unwrap();
+            if rank_increased {
+                self.rank[parent] -= 1;
+            }
+            self.parent[child] = child;
+            self.size[parent] -= self.size[child];
+            self.components += 1;
+        }
+    }
+
+    fn index_or_insert(&mut self, item: T) -> usize {
+        if let Some(i) = self.index.get(&item) {
+            return *i;
+        }
+        let i = self.items.len();
+        self.index.insert(item.clone(), i);
+        self.items.push(item);
+        self.parent.push(i);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        i
+    }
+
+    fn find_index(&self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            i = self.parent[i];
+        }
+        i
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut set = DisjointSet::from_iter(["a", "b", "c", "d", "e"]);
+        assert_eq!(set.component_count(), 5);
+        assert!(set.union("a", "b"));
+        assert!(set.union("c", "d"));
+        assert!(set.union("b", "d"));
+        assert!(!set.union("a", "c"));
+        assert!(set.connected(&"a", &"d"));
+        assert!(!set.connected(&"a", &"e"));
+        assert!(!set.connected(&"a", &"f"));
+        assert_eq!(set.component_size(&"c"), 4);
+        assert_eq!(set.component_size(&"e"), 1);
+        assert_eq!(set.component_count(), 2);
+        let root = set.find(&"a").copied();
+        assert_eq!(set.find(&"d").copied(), root);
+        assert_eq!(
+            set.components().collect::<Vec<_>>(),
+            vec![vec![&"a", &"b", &"c", &"d"], vec![&"e"]]
+        );
+    }
+
+    #[test]
+    fn test_union_inserts_items() {
+        let mut set = DisjointSet::new();
+        set.union(1, 2);
+        set.union(3, 4);
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.component_count(), 2);
+        assert!(!set.insert(3));
+        assert!(set.insert(5));
+        assert_eq!(set.component_count(), 3);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut set = RollbackDisjointSet::new();
+        set.union(1, 2);
+        let snapshot = set.snapshot();
+        set.union(2, 3);
+        set.union(4, 5);
+        set.union(3, 5);
+        assert!(set.connected(&1, &4));
+        assert_eq!(set.component_size(&1), 5);
+        assert_eq!(set.component_count(), 1);
+
+        set.rollback(snapshot);
+        assert!(set.connected(&1, &2));
+        assert!(!set.connected(&1, &3));
+        assert!(!set.connected(&4, &5));
+        assert_eq!(set.component_size(&1), 2);
+        assert_eq!(set.component_count(), 4);
+
+        assert!(set.union(1, 5));
+        assert!(set.connected(&2, &5));
+    }
+}
@@ -1,10 +1,22 @@
 use std::{collections::BTreeMap, fmt::Display, ops::RangeInclusive, str::FromStr};
 
+use super::DisjointSet;
+
+mod cell;
+mod distance_field;
 mod grid_iterator;
 mod grid_printer;
 mod path_finder;
+mod regions;
+mod scanning;
+mod transform;
+pub use cell::*;
+pub use distance_field::*;
 pub use grid_iterator::*;
-use grid_printer::*;
+pub use grid_printer::*;
+pub use regions::*;
+pub use scanning::*;
+pub use transform::*;
 
 pub struct Grid<K, D> {
     grid: BTreeMap<K, BTreeMap<K, D>>,
@@ -33,7 +45,8 @@ impl FromStr for Grid<usize, u32> {
             for (x, c) in line.chars().enumerate() {
                 grid.entry(y).or_default().insert(
                     x,
-                    c.to_digit(10).ok_or_else(|| "Invalid digit".to_string())?,
+                    c.to_digit(10)
+                        .ok_or_else(|| format!("Invalid digit '{c}' at ({x}, {y})"))?,
                 );
             }
         }
@@ -59,9 +72,9 @@ where
         self.grid.clear();
     }
     pub fn contains_key(&self, x: K, y: K) -> bool {
-        self.grid.get(&y).map_or(false, |row| row.contains_key(&x))
+        self.grid.get(&y).is_some_and(|row| row.contains_key(&x))
     }
-    pub fn entry(&mut self, x: K, y: K) -> std::collections::btree_map::Entry<K, D> {
+    pub fn entry(&mut self, x: K, y: K) -> std::collections::btree_map::Entry<'_, K, D> {
         self.grid.entry(y).or_default().entry(x)
     }
     pub fn get(&self, x: K, y: K) -> Option<&D> {
@@ -74,7 +87,7 @@ where
         self.grid.entry(y).or_default().insert(x, value)
     }
     pub fn into_values(self) -> impl Iterator<Item = D> {
-        self.grid.into_iter().flat_map(|(_, row)| row.into_values())
+        self.grid.into_values().flat_map(|row| row.into_values())
     }
     pub fn is_empty(&self) -> bool {
         self.grid.is_empty()
@@ -87,7 +100,7 @@ where
     pub fn keys(&self) -> impl Iterator<Item = (&K, &K)> {
         self.grid
             .iter()
-            .flat_map(|(y, row)| row.iter().map(move |(x, _)| (x, y)))
+            .flat_map(|(y, row)| row.keys().map(move |x| (x, y)))
     }
     pub fn len(&self) -> usize {
         self.grid.iter().map(|row| row.1.len()).sum()
@@ -110,9 +123,7 @@ where
         });
     }
     pub fn values(&self) -> impl Iterator<Item = &D> {
-        self.grid
-            .iter()
-            .flat_map(|(_, row)| row.iter().map(|(_, value)| value))
+        self.grid.values().flat_map(|row| row.values())
     }
 }
 
@@ -352,18 +363,58 @@ where
     K: Ord + Copy + Into<usize>,
     D: Eq,
 {
-    pub fn apply_path_finder(&self) -> path_finder::PathFinder<K, D> {
+    pub fn apply_path_finder(&self) -> path_finder::PathFinder<'_, K, D> {
         path_finder::PathFinder::new(self)
     }
 }
 
+impl<D> Grid<usize, D>
+where
+    D: Eq,
+{
+    /// Find connected regions of cells, see [`RegionFinder`]
+    pub fn regions(&self) -> RegionFinder<'_, D> {
+        RegionFinder::new(self)
+    }
+
+    /// Calculate distances from one or more sources, see [`DistanceFieldBuilder`]
+    pub fn distance_field(&self) -> DistanceFieldBuilder<'_, D> {
+        DistanceFieldBuilder::new(self)
+    }
+
+    /// Union all neighboring cells with an equal value
+    pub fn disjoint_set(&self, connectivity: Connectivity) -> DisjointSet<(usize, usize)> {
+        self.disjoint_set_by(connectivity, |a, b| a == b)
+    }
+
+    /// Union all neighboring cells for which `same_set` returns true
+    pub fn disjoint_set_by<F>(
+        &self,
+        connectivity: Connectivity,
+        same_set: F,
+    ) -> DisjointSet<(usize, usize)>
+    where
+        F: Fn(&D, &D) -> bool,
+    {
+        let mut set: DisjointSet<_> = self.keys().map(|(x, y)| (*x, *y)).collect();
+        for ((x, y), value) in self.iter() {
+            for (nx, ny) in connectivity.neighbors(*x, *y) {
+                if self.get(nx, ny).is_some_and(|next| same_set(value, next)) {
+                    set.union((*x, *y), (nx, ny));
+                }
+            }
+        }
+        set
+    }
+}
+
 impl<K, D> Grid<K, D>
 where
     K: Copy + Ord + std::ops::Add<Output = K> + TryFrom<u8> + Display,
     <K as TryFrom<u8>>::Error: std::fmt::Debug,
     D: Eq + ToString,
 {
-    pub fn printer(&self) -> GridPrinter<K, D> {
+    pub fn printer(&self) -> GridPrinter<'_, K, D> {
         GridPrinter::new(self)
     }
 }
@@ -389,6 +440,16 @@ mod test {
         assert_eq!(grid.to_string(), "123\n456\n789".to_string());
     }
 
+    #[test]
+    fn should_parse_digits() {
+        let grid: Grid<usize, u32> = "123\n456".parse().unwrap();
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(
+            "123\n4x6".parse::<Grid<usize, u32>>().err(),
+            Some("Invalid digit 'x' at (1, 1)".to_string())
+        );
+    }
+
     #[test]
     fn should_transpose_grid() {
         let mut grid: Grid<usize, char> = Grid::from("123\n456\n789".to_string());
@@ -396,6 +457,20 @@ mod test {
         assert_eq!(grid.to_string(), "147\n258\n369".to_string());
     }
 
+    #[test]
+    fn should_create_disjoint_set() {
+        let grid: Grid<usize, char> = Grid::from("AAB\nBAB\nBBA".to_string());
+        let mut set = grid.disjoint_set(Connectivity::Four);
+        assert_eq!(set.component_count(), 4);
+        assert_eq!(set.component_size(&(0, 0)), 3);
+        assert!(set.connected(&(2, 0), &(2, 1)));
+        assert!(!set.connected(&(1, 1), &(2, 2)));
+
+        let mut set = grid.disjoint_set(Connectivity::Eight);
+        assert_eq!(set.component_count(), 2);
+        assert_eq!(set.component_size(&(2, 2)), 4);
+    }
+
     mod to_diagonal {
         use super::*;
 
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use super::Grid;
+
+type Coord = (usize, usize);
+
+/// Typed cell of a grid which is parsed from a single character
+///
+/// Use `#[derive(GridCell)]` on an enum to implement this trait.
+pub trait GridCell: Sized {
+    /// Cell for the character, `None` for unknown characters
+    fn from_char(c: char) -> Option<Self>;
+    /// Character representing the cell
+    fn to_char(&self) -> char;
+}
+
+/// Positions of marker characters found by [`Grid::parse_with_markers`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Markers {
+    positions: HashMap<char, Vec<Coord>>,
+}
+
+impl Markers {
+    /// First position of the marker
+    pub fn get(&self, marker: char) -> Option<Coord> {
+        self.get_all(marker).first().copied()
+    }
+    /// All positions of the marker, in reading order
+    pub fn get_all(&self, marker: char) -> &[Coord] {
+        self.positions.get(&marker).map_or(&[], |p| p.as_slice())
+    }
+}
+
+impl<T: GridCell> Grid<usize, T> {
+    /// Parse every character into a cell, fails on unknown characters
+    pub fn parse_cells(input: &str) -> Result<Self, String> {
+        let mut grid = Self::default();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid.insert(x, y, parse_cell(c, x, y)?);
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Parse every character into a cell, the marker characters are stored
+    /// separately and replaced with the base cell
+    pub fn parse_with_markers(
+        input: &str,
+        markers: &[char],
+        base: T,
+    ) -> Result<(Self, Markers), String>
+    where
+        T: Clone,
+    {
+        let mut grid = Self::default();
+        let mut found = Markers::default();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let cell = if markers.contains(&c) {
+                    found.positions.entry(c).or_default().push((x, y));
+                    base.clone()
+                } else {
+                    parse_cell(c, x, y)?
+                };
+                grid.insert(x, y, cell);
+            }
+        }
+        Ok((grid, found))
+    }
+
+    /// Convert the cells back to characters, for printing
+    pub fn to_char_grid(&self) -> Grid<usize, char> {
+        let mut grid = Grid::default();
+        for ((x, y), cell) in self.iter() {
+            grid.insert(*x, *y, cell.to_char());
+        }
+        grid
+    }
+}
+
+fn parse_cell<T: GridCell>(c: char, x: usize, y: usize) -> Result<T, String> {
+    T::from_char(c).ok_or_else(|| format!("Unknown cell '{c}' at ({x}, {y})"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Open,
+    }
+
+    impl GridCell for Tile {
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                '#' => Some(Tile::Wall),
+                '.' => Some(Tile::Open),
+                _ => None,
+            }
+        }
+        fn to_char(&self) -> char {
+            match self {
+                Tile::Wall => '#',
+                Tile::Open => '.',
+            }
+        }
+    }
+
+    #[test]
+    fn should_parse_cells() {
+        let grid: Grid<usize, Tile> = Grid::parse_cells("#.\n.#").unwrap();
+        assert_eq!(grid.get(0, 0), Some(&Tile::Wall));
+        assert_eq!(grid.get(1, 0), Some(&Tile::Open));
+        assert_eq!(grid.to_char_grid().to_string(), "#.\n.#");
+        assert_eq!(
+            Grid::<usize, Tile>::parse_cells("#.\n.S").err(),
+            Some("Unknown cell 'S' at (1, 1)".to_string())
+        );
+    }
+
+    #[test]
+    fn should_parse_markers() {
+        let (grid, markers) =
+            Grid::parse_with_markers("#S.\n.E#\nS..", &['S', 'E'], Tile::Open).unwrap();
+        assert_eq!(grid.get(1, 0), Some(&Tile::Open));
+        assert_eq!(grid.to_char_grid().to_string(), "#..\n..#\n...");
+        assert_eq!(markers.get('S'), Some((1, 0)));
+        assert_eq!(markers.get_all('S'), [(1, 0), (0, 2)]);
+        assert_eq!(markers.get('E'), Some((1, 1)));
+        assert_eq!(markers.get('X'), None);
+        assert!(Grid::<usize, Tile>::parse_with_markers("#X", &['S'], Tile::Open).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::Grid;
+
+type Coord = (usize, usize);
+type PassableFn<D> = dyn Fn(&D) -> bool;
+
+/// A builder for a breadth first distance field over a grid
+///
+/// Every cell present in the grid is passable unless excluded by
+/// `with_obstacles` or `with_passable`.
+pub struct DistanceFieldBuilder<'g, D> {
+    grid: &'g Grid<usize, D>,
+    sources: Vec<Coord>,
+    obstacles: Vec<D>,
+    passable: Option<Box<PassableFn<D>>>,
+}
+
+impl<'g, D> DistanceFieldBuilder<'g, D>
+where
+    D: Eq,
+{
+    pub(super) fn new(grid: &'g Grid<usize, D>) -> Self {
+        Self {
+            grid,
+            sources: Vec::new(),
+            obstacles: Vec::new(),
+            passable: None,
+        }
+    }
+    /// Start the search from the given coordinates, this can be called multiple times
+    pub fn with_source_coords<T: IntoIterator<Item = Coord>>(mut self, coords: T) -> Self {
+        self.sources.extend(coords);
+        self
+    }
+    /// Start the search from all cells with the given value
+    pub fn with_sources(mut self, value: D) -> Self {
+        self.sources.extend(self.grid.collect_cells_iter(value));
+        self
+    }
+    /// Cells with the given value cannot be passed, this can be called multiple times
+    pub fn with_obstacles(mut self, value: D) -> Self {
+        self.obstacles.push(value);
+        self
+    }
+    /// Only cells matching the predicate can be passed
+    pub fn with_passable<F>(mut self, passable: F) -> Self
+    where
+        F: Fn(&D) -> bool + 'static,
+    {
+        self.passable = Some(Box::new(passable));
+        self
+    }
+
+    /// Run the search from all sources at once
+    pub fn run(&self) -> DistanceField {
+        self.run_from(&self.sources)
+    }
+
+    /// Compute the distance between every pair of passable cells, sources are ignored
+    ///
+    /// Memory grows with the square of the passable cells, only use this for small graphs.
+    pub fn run_all_pairs(&self) -> AllPairsDistances {
+        let fields = self
+            .grid
+            .iter()
+            .filter(|(_, value)| self.is_passable(value))
+            .map(|((x, y), _)| ((*x, *y), self.run_from(&[(*x, *y)])))
+            .collect();
+        AllPairsDistances { fields }
+    }
+
+    fn run_from(&self, sources: &[Coord]) -> DistanceField {
+        let width = self.grid.x_range().map_or(0, |r| r.end() + 1);
+        let height = self.grid.y_range().map_or(0, |r| r.end() + 1);
+        let mut field = DistanceField {
+            width,
+            height,
+            distances: vec![None; width * height],
+        };
+        let mut queue = VecDeque::new();
+        for (x, y) in sources.iter().copied() {
+            if self.grid.get(x, y).is_some_and(|v| self.is_passable(v)) && field.get(x, y).is_none()
+            {
+                field.distances[y * width + x] = Some(0);
+                queue.push_back((x, y));
+            }
+        }
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = field.distances[y * width + x].unwrap() + 1;
+            for (nx, ny) in self.grid.neighbors(x, y) {
+                if field.get(nx, ny).is_some() {
+                    continue;
+                }
+                if self.grid.get(nx, ny).is_some_and(|v| self.is_passable(v)) {
+                    field.distances[ny * width + nx] = Some(distance);
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        field
+    }
+
+    fn is_passable(&self, value: &D) -> bool {
+        !self.obstacles.contains(value) && self.passable.as_ref().is_none_or(|f| f(value))
+    }
+}
+
+/// Dense map of the distance from the nearest source to every reachable cell
+pub struct DistanceField {
+    width: usize,
+    height: usize,
+    distances: Vec<Option<usize>>,
+}
+
+impl DistanceField {
+    /// Distance to the nearest source, `None` when the cell cannot be reached
+    pub fn get(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.distances[y * self.width + x]
+    }
+    /// Iterate over all reachable cells and their distance
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, usize)> + '_ {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(|(i, d)| d.map(|d| ((i % self.width, i / self.width), d)))
+    }
+    /// Largest distance to any reachable cell
+    pub fn max_distance(&self) -> Option<usize> {
+        self.distances.iter().flatten().max().copied()
+    }
+
+    /// Find all shortcuts from a cell of this field to a cell of `to_end` within
+    /// the given Manhattan `radius`, ignoring any obstacles on the way
+    ///
+    /// This field should be calculated from the start and `to_end` from the end,
+    /// only shortcuts shorter than the regular path are returned.
+    pub fn shortcuts<'a>(
+        &'a self,
+        to_end: &'a DistanceField,
+        radius: usize,
+    ) -> impl Iterator<Item = Shortcut> + 'a {
+        let length = self
+            .iter()
+            .filter_map(|((x, y), d)| Some(d + to_end.get(x, y)?))
+            .min();
+        self.iter()
+            .filter(move |_| length.is_some())
+            .flat_map(move |((x, y), from_start)| {
+                let length = length.unwrap();
+                let r = radius as isize;
+                (-r..=r).flat_map(move |dy| {
+                    let dx_range = r - dy.abs();
+                    (-dx_range..=dx_range).filter_map(move |dx| {
+                        let to = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                        let shortcut_length = (dx.unsigned_abs() + dy.unsigned_abs())
+                            + from_start
+                            + to_end.get(to.0, to.1)?;
+                        if shortcut_length >= length {
+                            return None;
+                        }
+                        Some(Shortcut {
+                            from: (x, y),
+                            to,
+                            length: shortcut_length,
+                            saved: length - shortcut_length,
+                        })
+                    })
+                })
+            })
+    }
+}
+
+/// A shortcut between two cells, see [`DistanceField::shortcuts`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    pub from: Coord,
+    pub to: Coord,
+    /// Total length of the path using the shortcut
+    pub length: usize,
+    /// Distance saved compared to the regular path
+    pub saved: usize,
+}
+
+/// Distances between every pair of passable cells
+pub struct AllPairsDistances {
+    fields: HashMap<Coord, DistanceField>,
+}
+
+impl AllPairsDistances {
+    pub fn get(&self, from: Coord, to: Coord) -> Option<usize> {
+        self.fields.get(&from)?.get(to.0, to.1)
+    }
+    /// Distance field with `from` as the only source
+    pub fn field(&self, from: Coord) -> Option<&DistanceField> {
+        self.fields.get(&from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /*
+    S.#.
+    .##.
+    ...E
+    */
+    fn create_grid() -> Grid<usize, char> {
+        Grid::from("S.#.\n.##.\n...E".to_string())
+    }
+
+    #[test]
+    fn should_calculate_distances() {
+        let grid = create_grid();
+        let field = grid
+            .distance_field()
+            .with_sources('S')
+            .with_obstacles('#')
+            .run();
+        assert_eq!(field.get(0, 0), Some(0));
+        assert_eq!(field.get(3, 2), Some(5));
+        assert_eq!(field.get(3, 0), Some(7));
+        assert_eq!(field.get(2, 0), None);
+        assert_eq!(field.get(9, 9), None);
+        assert_eq!(field.max_distance(), Some(7));
+        assert_eq!(field.iter().count(), 9);
+    }
+
+    #[test]
+    fn should_calculate_from_multiple_sources() {
+        let grid = create_grid();
+        let field = grid
+            .distance_field()
+            .with_source_coords([(0, 0), (3, 0)])
+            .with_passable(|c| *c != '#')
+            .run();
+        assert_eq!(field.get(3, 2), Some(2));
+        assert_eq!(field.get(1, 2), Some(3));
+    }
+
+    /*
+    S#E
+    .#.
+    ...
+    */
+    #[test]
+    fn should_find_shortcuts() {
+        let grid: Grid<usize, char> = Grid::from("S#E\n.#.\n...".to_string());
+        let from_start = grid
+            .distance_field()
+            .with_sources('S')
+            .with_obstacles('#')
+            .run();
+        let from_end = grid
+            .distance_field()
+            .with_sources('E')
+            .with_obstacles('#')
+            .run();
+        let mut shortcuts = from_start.shortcuts(&from_end, 2).collect::<Vec<_>>();
+        shortcuts.sort_by_key(|s| s.from);
+        assert_eq!(
+            shortcuts,
+            vec![
+                Shortcut {
+                    from: (0, 0),
+                    to: (2, 0),
+                    length: 2,
+                    saved: 4
+                },
+                Shortcut {
+                    from: (0, 1),
+                    to: (2, 1),
+                    length: 4,
+                    saved: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_calculate_all_pairs() {
+        let grid = create_grid();
+        let distances = grid.distance_field().with_obstacles('#').run_all_pairs();
+        assert_eq!(distances.get((0, 0), (3, 2)), Some(5));
+        assert_eq!(distances.get((3, 2), (0, 0)), Some(5));
+        assert_eq!(distances.get((3, 0), (1, 0)), Some(8));
+        assert_eq!(distances.get((2, 0), (1, 0)), None);
+    }
+}
@@ -57,7 +57,7 @@ where
             x = *self.x_range.start();
             y = y.add(self.one);
         }
-        std::mem::replace(&mut self.head, Some((x, y)))
+        self.head.replace((x, y))
     }
 }
 
@@ -77,7 +77,7 @@ where
         } else {
             x = x.sub(self.one);
         }
-        std::mem::replace(&mut self.tail, Some((x, y)))
+        self.tail.replace((x, y))
     }
 }
 
@@ -89,7 +89,7 @@ mod test {
     fn test_grid_iterator() {
         let grid = GridIterator::<u32>::new(0..=2, 0..=2);
         assert_eq!(
-            grid.map(|(x, y)| (x, y)).collect::<Vec<_>>(),
+            grid.collect::<Vec<_>>(),
             vec![
                 (0, 0),
                 (1, 0),
@@ -108,7 +108,7 @@ mod test {
     fn test_grid_iterator_rev() {
         let grid = GridIterator::<u32>::new(0..=2, 0..=2);
         assert_eq!(
-            grid.rev().map(|(x, y)| (x, y)).collect::<Vec<_>>(),
+            grid.rev().collect::<Vec<_>>(),
             vec![
                 (2, 2),
                 (1, 2),
@@ -1,7 +1,18 @@
 use super::Grid;
-use std::fmt::Display;
+use std::{
+    collections::BTreeSet,
+    fmt::{Display, Write},
+    io::IsTerminal,
+};
+
+mod image;
+mod terminal;
+pub use image::*;
+pub use terminal::*;
 
 type OverrideFn<K> = dyn Fn((K, K)) -> Option<String>;
+type ColorFn<D> = dyn Fn(&D) -> Option<Color>;
+type StyleFn<K, D> = dyn Fn((K, K), &D) -> Option<Style>;
 
 pub struct GridPrinter<'g, K, D> {
     grid: &'g Grid<K, D>,
@@ -9,6 +20,15 @@ pub struct GridPrinter<'g, K, D> {
     cell_width: usize,
     cell_fill: Vec<D>,
     cell_override_fn: Option<Box<OverrideFn<K>>>,
+    colors: Vec<(D, Color)>,
+    color_fns: Vec<Box<ColorFn<D>>>,
+    paths: Vec<(Vec<(K, K)>, Color)>,
+    background: Color,
+    scale: usize,
+    styles: Vec<(D, Style)>,
+    style_fns: Vec<Box<StyleFn<K, D>>>,
+    highlights: Vec<(BTreeSet<(K, K)>, Style)>,
+    ansi: Option<bool>,
 }
 impl<'g, K, D> GridPrinter<'g, K, D>
 where
@@ -23,6 +43,15 @@ where
             cell_width: 1,
             cell_fill: Vec::new(),
             cell_override_fn: None,
+            colors: Vec::new(),
+            color_fns: Vec::new(),
+            paths: Vec::new(),
+            background: Color::WHITE,
+            scale: 8,
+            styles: Vec::new(),
+            style_fns: Vec::new(),
+            highlights: Vec::new(),
+            ansi: None,
         }
     }
     /// Print an X/Y legend on the side of the grid
@@ -48,9 +77,123 @@ where
         self.cell_override_fn = Some(Box::new(cell_override_fn));
         self
     }
+    /// Draw cells with the given value in a color, only used for images
+    pub fn with_color(mut self, value: D, color: Color) -> Self {
+        self.colors.push((value, color));
+        self
+    }
+    /// Map cell values to colors, cells without a color fall back to the
+    /// next mapping, only used for images
+    pub fn with_color_fn<F>(mut self, color_fn: F) -> Self
+    where
+        F: Fn(&D) -> Option<Color> + 'static,
+    {
+        self.color_fns.push(Box::new(color_fn));
+        self
+    }
+    /// Color numeric cells on a gradient from their lowest to their highest value,
+    /// only used for images
+    pub fn with_heatmap<F>(mut self, value_fn: F) -> Self
+    where
+        F: Fn(&D) -> Option<f64> + 'static,
+    {
+        let (min, max) = self
+            .grid
+            .values()
+            .filter_map(&value_fn)
+            .fold((f64::MAX, f64::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+        self.color_fns.push(Box::new(move |value| {
+            let v = value_fn(value)?;
+            let t = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.0
+            };
+            Some(Color::heat(t))
+        }));
+        self
+    }
+    /// Draw a path on top of the grid, this can be called multiple times
+    ///
+    /// Images grow to fit points outside of the grid.
+    pub fn with_path<T: IntoIterator<Item = (K, K)>>(mut self, path: T, color: Color) -> Self {
+        self.paths.push((path.into_iter().collect(), color));
+        self
+    }
+    /// Color of empty cells in images, defaults to white
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+    /// Size of a cell in images, in pixels, defaults to 8
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+    /// Style cells with the given value, only used for terminal output
+    pub fn with_style(mut self, value: D, style: Style) -> Self {
+        self.styles.push((value, style));
+        self
+    }
+    /// Style a single cell, only used for terminal output
+    pub fn with_style_at(self, coord: (K, K), style: Style) -> Self {
+        self.with_highlight([coord], style)
+    }
+    /// Style cells based on their coordinate and value, only used for terminal output
+    pub fn with_style_fn<F>(mut self, style_fn: F) -> Self
+    where
+        F: Fn((K, K), &D) -> Option<Style> + 'static,
+    {
+        self.style_fns.push(Box::new(style_fn));
+        self
+    }
+    /// Style a set of cells like a path or region, later highlights take
+    /// precedence, only used for terminal output
+    pub fn with_highlight<T: IntoIterator<Item = (K, K)>>(
+        mut self,
+        coords: T,
+        style: Style,
+    ) -> Self {
+        self.highlights.push((coords.into_iter().collect(), style));
+        self
+    }
+    /// Force styled output on or off, by default styles are only printed when
+    /// stdout is a terminal
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = Some(ansi);
+        self
+    }
     /// Print grid
     pub fn print(self) {
-        println!("{}", self);
+        println!("{}", self.render(self.use_ansi()));
+    }
+
+    fn use_ansi(&self) -> bool {
+        self.ansi.unwrap_or_else(|| std::io::stdout().is_terminal())
+    }
+
+    fn render(&self, ansi: bool) -> String {
+        let mut output = String::new();
+        let _ = self.write_grid(&mut output, ansi);
+        output
+    }
+
+    fn style_at(&self, coord: (K, K), value: Option<&D>) -> Option<Style> {
+        self.highlights
+            .iter()
+            .rev()
+            .find(|(coords, _)| coords.contains(&coord))
+            .map(|(_, style)| *style)
+            .or_else(|| {
+                let value = value?;
+                self.styles
+                    .iter()
+                    .find(|(v, _)| v == value)
+                    .map(|(_, style)| *style)
+                    .or_else(|| self.style_fns.iter().find_map(|f| f(coord, value)))
+            })
     }
 
     fn format_value<T: Display>(&self, value: T) -> String {
@@ -68,6 +211,16 @@ where
     D: Eq + ToString,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_grid(f, self.ansi == Some(true))
+    }
+}
+impl<'g, K, D> GridPrinter<'g, K, D>
+where
+    K: Copy + Ord + std::ops::Add<Output = K> + TryFrom<u8> + Display,
+    <K as TryFrom<u8>>::Error: std::fmt::Debug,
+    D: Eq + ToString,
+{
+    fn write_grid<W: Write>(&self, f: &mut W, ansi: bool) -> std::fmt::Result {
         let grid_iter = self.grid.grid_iter();
         if self.legend {
             write!(f, "{}", self.format_value(' '))?;
@@ -105,7 +258,11 @@ where
                     }
                 })
             });
-            write!(f, "{}", self.format_value(cell))?;
+            let cell = self.format_value(cell);
+            match self.style_at((x, y), self.grid.get(x, y)).filter(|_| ansi) {
+                Some(style) => write!(f, "{}", style.paint(&cell))?,
+                None => write!(f, "{}", cell)?,
+            }
             last_y = Some(y);
         }
         Ok(())
@@ -0,0 +1,424 @@
+use std::fmt::Write;
+
+use super::GridPrinter;
+
+/// RGB color used when rendering grids to images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(60, 170, 60);
+    pub const BLUE: Color = Color::rgb(38, 110, 210);
+    pub const YELLOW: Color = Color::rgb(240, 200, 30);
+
+    // Colors for values without an explicit mapping
+    const PALETTE: [Color; 8] = [
+        Color::rgb(68, 68, 68),
+        Color::rgb(38, 139, 210),
+        Color::rgb(133, 153, 0),
+        Color::rgb(203, 75, 22),
+        Color::rgb(108, 113, 196),
+        Color::rgb(42, 161, 152),
+        Color::rgb(211, 54, 130),
+        Color::rgb(181, 137, 0),
+    ];
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Blend towards `other`, `t` is clamped between 0 and 1
+    pub fn lerp(&self, other: Color, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Self::rgb(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    /// Heatmap color, dark blue at 0 through red to yellow at 1
+    pub fn heat(t: f64) -> Self {
+        let (low, mid, high) = (Color::rgb(20, 20, 90), Color::RED, Color::YELLOW);
+        if t < 0.5 {
+            low.lerp(mid, t * 2.0)
+        } else {
+            mid.lerp(high, (t - 0.5) * 2.0)
+        }
+    }
+
+    /// Color in `#rrggbb` notation
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    fn for_text(text: &str) -> Self {
+        let hash = text.bytes().fold(0_usize, |hash, b| {
+            hash.wrapping_mul(31).wrapping_add(b as usize)
+        });
+        Self::PALETTE[hash % Self::PALETTE.len()]
+    }
+}
+
+/// Image rendering, cells are colored by `with_color`, `with_color_fn` and
+/// `with_heatmap` in that order, other values get a color based on their text
+impl<'g, D> GridPrinter<'g, usize, D>
+where
+    D: Eq + ToString,
+{
+    /// Render the grid as an SVG document
+    pub fn to_svg(&self) -> String {
+        let ((x0, y0), (width, height)) = self.image_bounds();
+        let s = self.scale;
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width * s,
+            h = height * s
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="100%" height="100%" fill="{}"/>"#,
+            self.background.to_hex()
+        );
+        for ((x, y), value) in self.grid.iter() {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                (x - x0) * s,
+                (y - y0) * s,
+                self.cell_color(value).to_hex()
+            );
+        }
+        for (path, color) in self.paths.iter() {
+            let points = path
+                .iter()
+                .map(|(x, y)| {
+                    let cx = (x - x0) * s + s / 2;
+                    let cy = (y - y0) * s + s / 2;
+                    format!("{cx},{cy}")
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{points}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="square" stroke-linejoin="miter"/>"#,
+                color.to_hex(),
+                s - 2 * self.path_margin()
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Render the grid as a PNG image
+    #[cfg(feature = "render")]
+    pub fn to_png(&self) -> std::io::Result<Vec<u8>> {
+        let (width, height, pixels) = self.to_rgb();
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(std::io::Error::other)?;
+        Ok(png)
+    }
+
+    /// Render the grid to RGB pixels, returns width, height and pixel data
+    #[cfg(feature = "render")]
+    fn to_rgb(&self) -> (usize, usize, Vec<u8>) {
+        let ((x0, y0), (width, height)) = self.image_bounds();
+        let s = self.scale;
+        let (width, height) = (width * s, height * s);
+        let mut pixels =
+            [self.background.r, self.background.g, self.background.b].repeat(width * height);
+        let mut fill =
+            |(left, top): (usize, usize), (right, bottom): (usize, usize), color: Color| {
+                for py in top..bottom.min(height) {
+                    for px in left..right.min(width) {
+                        let i = (py * width + px) * 3;
+                        pixels[i..i + 3].copy_from_slice(&[color.r, color.g, color.b]);
+                    }
+                }
+            };
+        for ((x, y), value) in self.grid.iter() {
+            let (px, py) = ((x - x0) * s, (y - y0) * s);
+            fill((px, py), (px + s, py + s), self.cell_color(value));
+        }
+        let m = self.path_margin();
+        for (path, color) in self.paths.iter() {
+            // Connect every step so the path is drawn as one line
+            let steps = path.iter().zip(path.iter().skip(1));
+            for (a, b) in path.iter().take(1).map(|a| (a, a)).chain(steps) {
+                let (left, right) = (a.0.min(b.0) - x0, a.0.max(b.0) - x0);
+                let (top, bottom) = (a.1.min(b.1) - y0, a.1.max(b.1) - y0);
+                fill(
+                    (left * s + m, top * s + m),
+                    ((right + 1) * s - m, (bottom + 1) * s - m),
+                    *color,
+                );
+            }
+        }
+        (width, height, pixels)
+    }
+
+    fn cell_color(&self, value: &D) -> Color {
+        self.colors
+            .iter()
+            .find(|(v, _)| v == value)
+            .map(|(_, color)| *color)
+            .or_else(|| self.color_fns.iter().find_map(|f| f(value)))
+            .unwrap_or_else(|| Color::for_text(&value.to_string()))
+    }
+
+    // Top left cell and size of the image in cells, covering the grid and every path
+    fn image_bounds(&self) -> ((usize, usize), (usize, usize)) {
+        let grid = match (self.grid.x_range(), self.grid.y_range()) {
+            (Some(x), Some(y)) => Some(((*x.start(), *y.start()), (*x.end(), *y.end()))),
+            _ => None,
+        };
+        let points = self
+            .paths
+            .iter()
+            .flat_map(|(path, _)| path.iter().map(|&p| (p, p)));
+        let bounds = grid
+            .into_iter()
+            .chain(points)
+            .reduce(|(min, max), (low, high)| {
+                (
+                    (min.0.min(low.0), min.1.min(low.1)),
+                    (max.0.max(high.0), max.1.max(high.1)),
+                )
+            });
+        match bounds {
+            Some(((x0, y0), (x1, y1))) => ((x0, y0), (x1 - x0 + 1, y1 - y0 + 1)),
+            None => ((0, 0), (0, 0)),
+        }
+    }
+
+    fn path_margin(&self) -> usize {
+        self.scale / 4
+    }
+}
+
+/// Records grids as frames of an animated GIF, used to watch simulations
+#[cfg(feature = "render")]
+pub struct GifRecorder {
+    frames: Vec<(usize, usize, Vec<u8>)>,
+    delay: u16,
+}
+
+#[cfg(feature = "render")]
+impl Default for GifRecorder {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            delay: 10,
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+impl GifRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Time between frames in milliseconds, GIF stores this in steps of 10ms
+    pub fn with_delay(mut self, millis: u64) -> Self {
+        self.delay = (millis / 10).min(u16::MAX as u64) as u16;
+        self
+    }
+    /// Render the printer as the next frame
+    pub fn add_frame<D: Eq + ToString>(&mut self, printer: &GridPrinter<'_, usize, D>) {
+        self.frames.push(printer.to_rgb());
+    }
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encode all frames as a looping GIF
+    ///
+    /// Frames share one palette when they use at most 256 colors, otherwise
+    /// every frame is quantized on its own.
+    pub fn to_gif(&self) -> std::io::Result<Vec<u8>> {
+        use std::{borrow::Cow, collections::HashMap, io};
+
+        let to_u16 = |v: usize| {
+            u16::try_from(v).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "GIF frames are limited to 65535 pixels",
+                )
+            })
+        };
+        let width = to_u16(self.frames.iter().map(|(w, _, _)| *w).max().unwrap_or(0))?;
+        let height = to_u16(self.frames.iter().map(|(_, h, _)| *h).max().unwrap_or(0))?;
+
+        let mut colors: HashMap<&[u8], u8> = HashMap::new();
+        for pixel in self
+            .frames
+            .iter()
+            .flat_map(|(_, _, pixels)| pixels.chunks(3))
+        {
+            if colors.len() > 256 {
+                break;
+            }
+            let index = colors.len();
+            colors.entry(pixel).or_insert(index as u8);
+        }
+        let palette = if colors.len() <= 256 {
+            let mut palette = vec![0; colors.len() * 3];
+            for (color, index) in colors.iter() {
+                palette[*index as usize * 3..*index as usize * 3 + 3].copy_from_slice(color);
+            }
+            Some(palette)
+        } else {
+            None
+        };
+
+        let mut gif = Vec::new();
+        {
+            let mut encoder =
+                gif::Encoder::new(&mut gif, width, height, palette.as_deref().unwrap_or(&[]))
+                    .map_err(io::Error::other)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(io::Error::other)?;
+            for (w, h, pixels) in self.frames.iter() {
+                let mut frame = match palette {
+                    Some(_) => gif::Frame {
+                        width: to_u16(*w)?,
+                        height: to_u16(*h)?,
+                        buffer: Cow::Owned(pixels.chunks(3).map(|c| colors[c]).collect()),
+                        ..Default::default()
+                    },
+                    None => gif::Frame::from_rgb_speed(to_u16(*w)?, to_u16(*h)?, pixels, 10),
+                };
+                frame.delay = self.delay;
+                encoder.write_frame(&frame).map_err(io::Error::other)?;
+            }
+        }
+        Ok(gif)
+    }
+
+    /// Write the GIF to a file
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_gif()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tools::Grid;
+
+    fn create_grid() -> Grid<usize, char> {
+        Grid::from("#..\n.#.\n...".to_string())
+    }
+
+    #[test]
+    fn should_render_svg() {
+        let grid = create_grid();
+        let svg = grid
+            .printer()
+            .with_color('#', Color::BLACK)
+            .with_color('.', Color::WHITE)
+            .with_scale(10)
+            .with_path([(0, 1), (0, 2), (1, 2)], Color::RED)
+            .to_svg();
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="30""#)
+        );
+        assert_eq!(svg.matches(r##"fill="#000000""##).count(), 2);
+        assert!(svg.contains(r##"<rect x="10" y="10" width="10" height="10" fill="#000000"/>"##));
+        assert!(svg.contains(r#"points="5,15 5,25 15,25""#));
+    }
+
+    #[test]
+    fn should_fit_paths_outside_of_the_grid() {
+        let mut grid: Grid<usize, char> = Grid::default();
+        grid.insert(2, 2, '#');
+        let printer = grid
+            .printer()
+            .with_scale(10)
+            .with_path([(0, 0), (2, 2)], Color::RED);
+        assert_eq!(printer.image_bounds(), ((0, 0), (3, 3)));
+        let svg = printer.to_svg();
+        assert!(svg.contains(r#"width="30" height="30""#));
+        assert!(svg.contains(r#"<rect x="20" y="20" width="10" height="10""#));
+        assert!(svg.contains(r#"points="5,5 25,25""#));
+    }
+
+    #[test]
+    fn should_map_colors() {
+        let grid: Grid<usize, char> = Grid::from("0#9\n5#.".to_string());
+        let printer = grid
+            .printer()
+            .with_color('#', Color::BLACK)
+            .with_heatmap(|c| c.to_digit(10).map(|d| d as f64));
+        assert_eq!(printer.cell_color(&'#'), Color::BLACK);
+        assert_eq!(printer.cell_color(&'0'), Color::heat(0.0));
+        assert_eq!(printer.cell_color(&'9'), Color::YELLOW);
+        assert_eq!(printer.cell_color(&'5'), Color::heat(5.0 / 9.0));
+        assert_eq!(printer.cell_color(&'.'), Color::for_text("."));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_render_png() {
+        let grid = create_grid();
+        let png = grid
+            .printer()
+            .with_color('#', Color::BLACK)
+            .with_color('.', Color::WHITE)
+            .with_scale(4)
+            .with_path([(2, 0), (2, 1)], Color::RED)
+            .to_png()
+            .unwrap();
+        let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (12, 12));
+        let pixel = |x: usize, y: usize| &pixels[(y * 12 + x) * 3..(y * 12 + x) * 3 + 3];
+        assert_eq!(pixel(0, 0), [0, 0, 0]);
+        assert_eq!(pixel(4, 0), [255, 255, 255]);
+        assert_eq!(pixel(9, 5), [220, 50, 47]);
+        assert_eq!(pixel(8, 5), [255, 255, 255]);
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_record_gif() {
+        let mut grid = create_grid();
+        let mut recorder = GifRecorder::new().with_delay(100);
+        recorder.add_frame(&grid.printer());
+        grid.insert(2, 2, '#');
+        recorder.add_frame(&grid.printer().with_scale(2));
+        assert_eq!(recorder.len(), 2);
+
+        let gif = recorder.to_gif().unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (24, 24));
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    fmt::Display,
+    io::{IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+use super::{Color, GridPrinter};
+
+/// Terminal style of a cell, printed using ANSI escape codes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+    pub fn with_bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Wrap the text in the escape codes of this style
+    pub fn paint(&self, text: &str) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if let Some(Color { r, g, b }) = self.fg {
+            codes.push(format!("38;2;{r};{g};{b}"));
+        }
+        if let Some(Color { r, g, b }) = self.bg {
+            codes.push(format!("48;2;{r};{g};{b}"));
+        }
+        if codes.is_empty() {
+            return text.to_string();
+        }
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+/// Redraws a grid in place to follow a simulation step by step
+///
+/// When stdout is not a terminal every frame is printed below the previous
+/// one as plain text, without waiting between frames.
+pub struct LiveView {
+    interval: Duration,
+    last_frame: Option<Instant>,
+    lines: usize,
+    tty: bool,
+}
+
+impl LiveView {
+    /// Redraw at most `fps` times per second, 0 draws as fast as possible
+    pub fn new(fps: u32) -> Self {
+        Self {
+            interval: if fps > 0 {
+                Duration::from_secs_f64(1.0 / fps as f64)
+            } else {
+                Duration::ZERO
+            },
+            last_frame: None,
+            lines: 0,
+            tty: std::io::stdout().is_terminal(),
+        }
+    }
+    /// Override the terminal detection
+    pub fn with_tty(mut self, tty: bool) -> Self {
+        self.tty = tty;
+        self
+    }
+
+    /// Draw the next frame, waits until the frame is due
+    pub fn draw<K, D>(&mut self, printer: &GridPrinter<'_, K, D>)
+    where
+        K: Copy + Ord + std::ops::Add<Output = K> + TryFrom<u8> + Display,
+        <K as TryFrom<u8>>::Error: std::fmt::Debug,
+        D: Eq + ToString,
+    {
+        let frame = self.frame(printer);
+        if self.tty {
+            if let Some(last_frame) = self.last_frame {
+                std::thread::sleep(self.interval.saturating_sub(last_frame.elapsed()));
+            }
+            self.last_frame = Some(Instant::now());
+        }
+        let mut stdout = std::io::stdout().lock();
+        let _ = stdout.write_all(frame.as_bytes());
+        let _ = stdout.flush();
+    }
+
+    fn frame<K, D>(&mut self, printer: &GridPrinter<'_, K, D>) -> String
+    where
+        K: Copy + Ord + std::ops::Add<Output = K> + TryFrom<u8> + Display,
+        <K as TryFrom<u8>>::Error: std::fmt::Debug,
+        D: Eq + ToString,
+    {
+        let content = printer.render(printer.ansi.unwrap_or(self.tty));
+        let mut frame = String::new();
+        if self.tty && self.lines > 0 {
+            // Move the cursor to the start of the previous frame and clear it
+            frame.push_str(&format!("\x1b[{}F\x1b[J", self.lines));
+        } else if self.lines > 0 {
+            frame.push('\n');
+        }
+        self.lines = content.lines().count();
+        frame.push_str(&content);
+        frame.push('\n');
+        frame
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tools::Grid;
+
+    fn create_grid() -> Grid<usize, char> {
+        Grid::from("#..\n.#.\n...".to_string())
+    }
+
+    #[test]
+    fn should_paint_styles() {
+        assert_eq!(Style::new().paint("#"), "#");
+        assert_eq!(
+            Style::new().with_bold().with_fg(Color::RED).paint("#"),
+            "\x1b[1;38;2;220;50;47m#\x1b[0m"
+        );
+        assert_eq!(
+            Style::new().with_bg(Color::BLACK).paint("#"),
+            "\x1b[48;2;0;0;0m#\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn should_print_styles() {
+        let grid = create_grid();
+        let wall = Style::new().with_bold();
+        let path = Style::new().with_fg(Color::GREEN);
+        let printer = grid
+            .printer()
+            .with_style('#', wall)
+            .with_highlight([(0, 0), (1, 0), (2, 0)], path)
+            .with_style_at((2, 0), Style::new().with_fg(Color::RED));
+        assert_eq!(printer.to_string(), "#..\n.#.\n...");
+        assert_eq!(
+            printer.with_ansi(true).to_string(),
+            format!(
+                "{}{}{}\n.{}.\n...",
+                path.paint("#"),
+                path.paint("."),
+                Style::new().with_fg(Color::RED).paint("."),
+                wall.paint("#")
+            )
+        );
+    }
+
+    #[test]
+    fn should_redraw_frames() {
+        let mut grid = create_grid();
+        let mut view = LiveView::new(0).with_tty(true);
+        assert_eq!(view.frame(&grid.printer()), "#..\n.#.\n...\n");
+        grid.insert(2, 2, '#');
+        assert_eq!(view.frame(&grid.printer()), "\x1b[3F\x1b[J#..\n.#.\n..#\n");
+
+        let mut view = LiveView::new(0).with_tty(false);
+        let printer = grid.printer().with_style('#', Style::new().with_bold());
+        assert_eq!(view.frame(&printer), "#..\n.#.\n..#\n");
+        assert_eq!(view.frame(&printer), "\n#..\n.#.\n..#\n");
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::tools::{BfsBuilder, DijkstraBuilder};
+use crate::tools::{AStarBuilder, BfsBuilder, DijkstraBuilder, IncrementalSearch};
 
 use super::Grid;
 
@@ -75,4 +75,26 @@ where
         };
         DijkstraBuilder::new(start, end).with_obstacles(self.obstacles)
     }
+    pub fn astar(self) -> AStarBuilder {
+        let (start, end) = match (self.start, self.end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => panic!("Start and end coordinates must be set"),
+        };
+        AStarBuilder::new(start, end).with_obstacles(self.obstacles)
+    }
+    /// Search which supports adding obstacles later on, bounded by the grid
+    pub fn incremental(self) -> IncrementalSearch {
+        let (start, end) = match (self.start, self.end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => panic!("Start and end coordinates must be set"),
+        };
+        let search = IncrementalSearch::new(start, end).with_obstacles(self.obstacles);
+        match (self.grid.x_range(), self.grid.y_range()) {
+            (Some(x_range), Some(y_range)) => search.with_bounds(
+                ((*x_range.start()).into(), (*y_range.start()).into()),
+                ((*x_range.end()).into(), (*y_range.end()).into()),
+            ),
+            _ => search,
+        }
+    }
 }
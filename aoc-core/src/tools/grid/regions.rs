@@ -0,0 +1,276 @@
+use std::collections::BTreeSet;
+
+use super::Grid;
+
+type Coord = (usize, usize);
+type PredicateFn<D> = dyn Fn(&D) -> bool;
+
+/// Which cells are considered to be touching each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Only horizontal and vertical neighbors
+    Four,
+    /// Horizontal, vertical and diagonal neighbors
+    Eight,
+}
+
+impl Connectivity {
+    pub fn neighbors(&self, x: usize, y: usize) -> Vec<Coord> {
+        let deltas: &[(isize, isize)] = match self {
+            Connectivity::Four => &[(-1, 0), (0, -1), (1, 0), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        };
+        deltas
+            .iter()
+            .filter_map(|(dx, dy)| Some((x.checked_add_signed(*dx)?, y.checked_add_signed(*dy)?)))
+            .collect()
+    }
+}
+
+/// A builder to find connected regions (flood fill) in a grid
+///
+/// By default cells are grouped by equal value and only horizontal and vertical
+/// neighbors are connected.
+pub struct RegionFinder<'g, D> {
+    grid: &'g Grid<usize, D>,
+    connectivity: Connectivity,
+    predicate: Option<Box<PredicateFn<D>>>,
+}
+
+impl<'g, D> RegionFinder<'g, D>
+where
+    D: Eq,
+{
+    pub(super) fn new(grid: &'g Grid<usize, D>) -> Self {
+        Self {
+            grid,
+            connectivity: Connectivity::Four,
+            predicate: None,
+        }
+    }
+
+    /// Use a different connectivity, defaults to [`Connectivity::Four`]
+    pub fn with_connectivity(mut self, connectivity: Connectivity) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
+    /// Group all cells matching the predicate regardless of their value, cells
+    /// not matching the predicate are not part of any region
+    pub fn with_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&D) -> bool + 'static,
+    {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Find all regions, labelled in the order of their top left cell
+    pub fn find(&self) -> Vec<Region<'g, D>> {
+        let mut visited = BTreeSet::new();
+        let mut regions = Vec::new();
+        for ((x, y), _) in self.grid.iter() {
+            if visited.contains(&(*x, *y)) {
+                continue;
+            }
+            if let Some(region) = self.fill((*x, *y), regions.len()) {
+                visited.extend(region.cells.iter().copied());
+                regions.push(region);
+            }
+        }
+        regions
+    }
+
+    /// Flood fill the region containing the given cell
+    pub fn find_at(&self, x: usize, y: usize) -> Option<Region<'g, D>> {
+        self.fill((x, y), 0)
+    }
+
+    fn fill(&self, start: Coord, label: usize) -> Option<Region<'g, D>> {
+        let value = self.grid.get(start.0, start.1)?;
+        if !self.belongs_to(value, value) {
+            return None;
+        }
+        let mut cells = BTreeSet::from([start]);
+        let mut queue = vec![start];
+        while let Some((x, y)) = queue.pop() {
+            for (nx, ny) in self.connectivity.neighbors(x, y) {
+                if cells.contains(&(nx, ny)) {
+                    continue;
+                }
+                match self.grid.get(nx, ny) {
+                    Some(next) if self.belongs_to(value, next) => {
+                        cells.insert((nx, ny));
+                        queue.push((nx, ny));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Some(Region {
+            label,
+            value,
+            cells,
+        })
+    }
+
+    fn belongs_to(&self, region_value: &D, value: &D) -> bool {
+        match &self.predicate {
+            Some(predicate) => predicate(value),
+            None => region_value == value,
+        }
+    }
+}
+
+/// A connected set of cells in a grid
+pub struct Region<'g, D> {
+    label: usize,
+    value: &'g D,
+    cells: BTreeSet<Coord>,
+}
+
+impl<D> Region<'_, D> {
+    pub fn label(&self) -> usize {
+        self.label
+    }
+    /// Value of the first cell of the region
+    pub fn value(&self) -> &D {
+        self.value
+    }
+    pub fn cells(&self) -> &BTreeSet<Coord> {
+        &self.cells
+    }
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.cells.contains(&(x, y))
+    }
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+    /// Amount of cell edges which are not shared with another cell of the region
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|(x, y)| 4 - self.count_neighbors(*x, *y))
+            .sum()
+    }
+    /// Amount of corners of the outline of the region, including holes
+    pub fn corner_count(&self) -> usize {
+        let mut corners = 0;
+        for (x, y) in self.cells.iter().map(|(x, y)| (*x as isize, *y as isize)) {
+            for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                let horizontal = self.contains_signed(x + dx, y);
+                let vertical = self.contains_signed(x, y + dy);
+                let diagonal = self.contains_signed(x + dx, y + dy);
+                // Outer corner when both sides are open, inner corner when only the diagonal is open
+                if (!horizontal && !vertical) || (horizontal && vertical && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+    /// Amount of straight sides of the outline of the region, which is equal to the amount of corners
+    pub fn side_count(&self) -> usize {
+        self.corner_count()
+    }
+    /// Top left and bottom right cell of the bounding box of the region
+    pub fn bounding_box(&self) -> (Coord, Coord) {
+        self.cells.iter().fold(
+            ((usize::MAX, usize::MAX), (0, 0)),
+            |(top_left, bottom_right), (x, y)| {
+                (
+                    (top_left.0.min(*x), top_left.1.min(*y)),
+                    (bottom_right.0.max(*x), bottom_right.1.max(*y)),
+                )
+            },
+        )
+    }
+    /// Cells of the region with at least one horizontal or vertical neighbor outside the region
+    pub fn boundary_cells(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.cells
+            .iter()
+            .filter(|(x, y)| self.count_neighbors(*x, *y) < 4)
+            .copied()
+    }
+
+    fn count_neighbors(&self, x: usize, y: usize) -> usize {
+        Connectivity::Four
+            .neighbors(x, y)
+            .into_iter()
+            .filter(|c| self.cells.contains(c))
+            .count()
+    }
+
+    fn contains_signed(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && self.cells.contains(&(x as usize, y as usize))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_grid() -> Grid<usize, char> {
+        Grid::from("AAAA\nBBCD\nBBCC\nEEEC".to_string())
+    }
+
+    #[test]
+    fn should_find_regions_by_value() {
+        let grid = create_grid();
+        let regions = grid.regions().find();
+        assert_eq!(
+            regions
+                .iter()
+                .map(|r| (*r.value(), r.area(), r.perimeter(), r.side_count()))
+                .collect::<Vec<_>>(),
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+        assert_eq!(
+            regions.iter().map(|r| r.label()).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn should_count_sides_with_holes() {
+        let grid: Grid<usize, char> = Grid::from("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO".to_string());
+        let region = grid.regions().find_at(0, 0).expect("Region");
+        assert_eq!(region.area(), 21);
+        assert_eq!(region.perimeter(), 36);
+        assert_eq!(region.side_count(), 20);
+        assert_eq!(region.boundary_cells().count(), 20);
+    }
+
+    #[test]
+    fn should_find_regions_by_predicate() {
+        let grid: Grid<usize, char> = Grid::from("#a#\n#b#\nc##".to_string());
+        let regions = grid.regions().with_predicate(|c| *c != '#').find();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].area(), 2);
+        assert_eq!(regions[0].bounding_box(), ((1, 0), (1, 1)));
+
+        let regions = grid
+            .regions()
+            .with_predicate(|c| *c != '#')
+            .with_connectivity(Connectivity::Eight)
+            .find();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].area(), 3);
+        assert_eq!(regions[0].bounding_box(), ((0, 0), (1, 2)));
+    }
+}
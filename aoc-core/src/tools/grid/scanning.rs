@@ -0,0 +1,276 @@
+use super::Grid;
+
+type Coord = (usize, usize);
+
+/// Reading direction through a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// All eight directions, clockwise starting up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+    /// Horizontal and vertical directions, clockwise starting up
+    pub const STRAIGHT: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Move one step in this direction, `None` when moving below 0
+    pub fn step(&self, (x, y): Coord) -> Option<Coord> {
+        let (dx, dy) = self.delta();
+        Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?))
+    }
+}
+
+impl<D> Grid<usize, D> {
+    /// Iterate over all lines of the grid in the given reading direction,
+    /// `Right` gives the rows, `Down` the columns and `DownRight`/`UpRight` the
+    /// diagonals and anti-diagonals
+    pub fn lines(&self, direction: Direction) -> impl Iterator<Item = GridLine<'_, D>> {
+        let bounds = match (self.x_range(), self.y_range()) {
+            (Some(x), Some(y)) => Some(((*x.start(), *y.start()), (*x.end(), *y.end()))),
+            _ => None,
+        };
+        let (dx, dy) = direction.delta();
+        bounds.into_iter().flat_map(move |((x0, y0), (x1, y1))| {
+            let in_bounds = move |(x, y): Coord| x >= x0 && x <= x1 && y >= y0 && y <= y1;
+            // A line starts at every cell where the previous cell is out of bounds
+            (y0..=y1)
+                .flat_map(move |y| (x0..=x1).map(move |x| (x, y)))
+                .filter(move |(x, y)| {
+                    let previous = x.checked_add_signed(-dx).zip(y.checked_add_signed(-dy));
+                    !previous.is_some_and(in_bounds)
+                })
+                .map(move |start| GridLine {
+                    grid: self,
+                    next: Some(start),
+                    direction,
+                    bounds: ((x0, y0), (x1, y1)),
+                })
+        })
+    }
+}
+
+/// Borrowed line through a grid, see [`Grid::lines`]
+///
+/// Empty cells on the line are skipped.
+pub struct GridLine<'g, D> {
+    grid: &'g Grid<usize, D>,
+    next: Option<Coord>,
+    direction: Direction,
+    bounds: (Coord, Coord),
+}
+
+impl<'g, D> Iterator for GridLine<'g, D> {
+    type Item = (Coord, &'g D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ((x0, y0), (x1, y1)) = self.bounds;
+        loop {
+            let (x, y) = self.next?;
+            self.next = self
+                .direction
+                .step((x, y))
+                .filter(|(x, y)| *x >= x0 && *x <= x1 && *y >= y0 && *y <= y1);
+            if let Some(value) = self.grid.get(x, y) {
+                return Some(((x, y), value));
+            }
+        }
+    }
+}
+
+impl<D> Grid<usize, D>
+where
+    D: PartialEq<char>,
+{
+    /// Find all occurrences of the pattern in the given directions, returns the
+    /// coordinate of the first character and the direction of each match
+    pub fn find_pattern(&self, pattern: &str, directions: &[Direction]) -> Vec<(Coord, Direction)> {
+        let pattern = pattern.chars().collect::<Vec<_>>();
+        let first = match pattern.first() {
+            Some(first) => *first,
+            None => return Vec::new(),
+        };
+        self.iter()
+            .filter(|(_, value)| **value == first)
+            .flat_map(|((x, y), _)| directions.iter().map(move |d| ((*x, *y), *d)))
+            .filter(|(start, direction)| {
+                let mut cur = Some(*start);
+                pattern.iter().all(|c| {
+                    let matches = cur
+                        .and_then(|(x, y)| self.get(x, y))
+                        .is_some_and(|value| *value == *c);
+                    cur = cur.and_then(|c| direction.step(c));
+                    matches
+                })
+            })
+            .collect()
+    }
+
+    /// Find all positions where the stencil matches, returns the top left coordinate of each match
+    pub fn find_stencil(&self, stencil: &Stencil) -> Vec<Coord> {
+        self.keys()
+            .map(|(x, y)| (*x, *y))
+            .filter(|(x, y)| {
+                stencil
+                    .cells
+                    .iter()
+                    .all(|((sx, sy), c)| self.get(x + sx, y + sy).is_some_and(|value| *value == *c))
+            })
+            .collect()
+    }
+}
+
+/// Small 2D pattern with wildcards, see [`Grid::find_stencil`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil {
+    // Cells which have to match, relative to the top left of the stencil
+    cells: Vec<(Coord, char)>,
+}
+
+impl Stencil {
+    /// Parse a stencil from lines of text, the wildcard character matches any cell
+    pub fn parse(pattern: &str, wildcard: char) -> Self {
+        let grid: Grid<usize, char> = Grid::from(pattern.to_string());
+        Self::from_grid(&grid, wildcard)
+    }
+
+    /// The stencil and its rotations by 90, 180 and 270 degrees, without duplicates
+    pub fn rotations(&self) -> Vec<Stencil> {
+        let mut grid: Grid<usize, Option<char>> = Grid::default();
+        for ((x, y), c) in self.cells.iter() {
+            grid.insert(*x, *y, Some(*c));
+        }
+        // Fill the corners so rotations keep the same bounding box
+        let (width, height) = self
+            .cells
+            .iter()
+            .fold((0, 0), |(w, h), ((x, y), _)| ((*x).max(w), (*y).max(h)));
+        grid.entry(0, 0).or_insert(None);
+        grid.entry(width, height).or_insert(None);
+
+        let mut rotations: Vec<Stencil> = Vec::new();
+        for _ in 0..4 {
+            let stencil = Stencil {
+                cells: grid
+                    .iter()
+                    .filter_map(|((x, y), c)| c.map(|c| ((*x, *y), c)))
+                    .collect(),
+            };
+            if !rotations.contains(&stencil) {
+                rotations.push(stencil);
+            }
+            grid = grid.rotate_cw();
+        }
+        rotations
+    }
+
+    fn from_grid(grid: &Grid<usize, char>, wildcard: char) -> Self {
+        Self {
+            cells: grid
+                .iter()
+                .filter(|(_, c)| **c != wildcard)
+                .map(|((x, y), c)| ((*x, *y), *c))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_grid() -> Grid<usize, char> {
+        Grid::from("abc\ndef\nghi".to_string())
+    }
+
+    fn collect_lines(grid: &Grid<usize, char>, direction: Direction) -> Vec<String> {
+        grid.lines(direction)
+            .map(|line| line.map(|(_, c)| c).collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn should_iterate_lines() {
+        let grid = create_grid();
+        assert_eq!(
+            collect_lines(&grid, Direction::Right),
+            ["abc", "def", "ghi"]
+        );
+        assert_eq!(collect_lines(&grid, Direction::Down), ["adg", "beh", "cfi"]);
+        assert_eq!(collect_lines(&grid, Direction::Left), ["cba", "fed", "ihg"]);
+        assert_eq!(
+            collect_lines(&grid, Direction::DownRight),
+            ["aei", "bf", "c", "dh", "g"]
+        );
+        assert_eq!(
+            collect_lines(&grid, Direction::UpRight),
+            ["a", "db", "gec", "hf", "i"]
+        );
+    }
+
+    #[test]
+    fn should_find_pattern() {
+        let grid: Grid<usize, char> = Grid::from("XMAS\nMM..\nA.A.\nS..S".to_string());
+        let mut found = grid.find_pattern("XMAS", &Direction::ALL);
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ((0, 0), Direction::Right),
+                ((0, 0), Direction::DownRight),
+                ((0, 0), Direction::Down)
+            ]
+        );
+        assert_eq!(grid.find_pattern("SAMX", &Direction::STRAIGHT).len(), 2);
+        assert!(grid.find_pattern("", &Direction::ALL).is_empty());
+    }
+
+    #[test]
+    fn should_find_stencil() {
+        let grid: Grid<usize, char> = Grid::from("M.S..\n.A...\nMSSS.\n..A..\n.M.M.".to_string());
+        let stencil = Stencil::parse("M.S\n.A.\nM.S", '.');
+        assert_eq!(grid.find_stencil(&stencil), [(0, 0)]);
+
+        let rotations = stencil.rotations();
+        assert_eq!(rotations.len(), 4);
+        let found = rotations
+            .iter()
+            .flat_map(|s| grid.find_stencil(s))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [(0, 0), (1, 2)]);
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::BTreeMap,
+    ops::{Add, RangeInclusive, Sub},
+};
+
+use super::Grid;
+
+/// Non-destructive transformations, all of these return a new grid
+///
+/// Transformed grids keep the top left corner of the original grid.
+impl<K, D> Grid<K, D>
+where
+    K: Copy + Ord + Add<Output = K> + Sub<Output = K> + TryFrom<u8>,
+    <K as TryFrom<u8>>::Error: std::fmt::Debug,
+    D: Clone,
+{
+    /// Rotate the grid 90 degrees clockwise
+    pub fn rotate_cw(&self) -> Self {
+        self.map_keys(|(x0, y0), (_, y1), (x, y)| (x0 + (y1 - y), y0 + (x - x0)))
+    }
+    /// Rotate the grid 90 degrees counter clockwise
+    pub fn rotate_ccw(&self) -> Self {
+        self.map_keys(|(x0, y0), (x1, _), (x, y)| (x0 + (y - y0), y0 + (x1 - x)))
+    }
+    /// Mirror the grid horizontally, left becomes right
+    pub fn flip_h(&self) -> Self {
+        self.map_keys(|(x0, _), (x1, _), (x, y)| (x0 + (x1 - x), y))
+    }
+    /// Mirror the grid vertically, top becomes bottom
+    pub fn flip_v(&self) -> Self {
+        self.map_keys(|(_, y0), (_, y1), (x, y)| (x, y0 + (y1 - y)))
+    }
+    /// Copy of the cells within the ranges, cells keep their coordinates; empty
+    /// when a range starts after its end
+    pub fn crop(&self, x_range: RangeInclusive<K>, y_range: RangeInclusive<K>) -> Self {
+        self.window(x_range, y_range).to_grid()
+    }
+    /// Surround the grid with `n` cells of `fill`, existing cells shift `n` to the bottom right
+    pub fn pad(&self, n: K, fill: D) -> Self {
+        let (x_range, y_range) = match (self.x_range(), self.y_range()) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Self::default(),
+        };
+        let mut grid = self.map_keys(|_, _, (x, y)| (x + n, y + n));
+        let x_end = *x_range.end() + n + n;
+        let y_end = *y_range.end() + n + n;
+        for (x, y) in super::GridIterator::new(*x_range.start()..=x_end, *y_range.start()..=y_end) {
+            let inside = x >= *x_range.start() + n
+                && x <= *x_range.end() + n
+                && y >= *y_range.start() + n
+                && y <= *y_range.end() + n;
+            if !inside {
+                grid.insert(x, y, fill.clone());
+            }
+        }
+        grid
+    }
+    /// Repeat the grid `nx` times horizontally and `ny` times vertically
+    pub fn tile(&self, nx: usize, ny: usize) -> Self {
+        let (x_range, y_range) = match (self.x_range(), self.y_range()) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Self::default(),
+        };
+        let one = Self::one();
+        let width = *x_range.end() - *x_range.start() + one;
+        let height = *y_range.end() - *y_range.start() + one;
+        let mut grid = Self::default();
+        let mut y_offset = None;
+        for _ in 0..ny {
+            let mut x_offset = None;
+            for _ in 0..nx {
+                for ((x, y), value) in self.iter() {
+                    let x = x_offset.map_or(*x, |o| *x + o);
+                    let y = y_offset.map_or(*y, |o| *y + o);
+                    grid.insert(x, y, value.clone());
+                }
+                x_offset = Some(x_offset.map_or(width, |o| o + width));
+            }
+            y_offset = Some(y_offset.map_or(height, |o| o + height));
+        }
+        grid
+    }
+    /// Rotate the grid 45 degrees counter clockwise, the mirrored version of `to_diagonal`
+    ///
+    /// Every row of the new grid is a top left to bottom right diagonal of the original.
+    pub fn anti_diagonal(&self) -> Self {
+        let mut grid = self.flip_h();
+        grid.to_diagonal();
+        grid
+    }
+
+    fn map_keys<F>(&self, f: F) -> Self
+    where
+        F: Fn((K, K), (K, K), (K, K)) -> (K, K),
+    {
+        let (x_range, y_range) = match (self.x_range(), self.y_range()) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Self::default(),
+        };
+        let start = (*x_range.start(), *y_range.start());
+        let end = (*x_range.end(), *y_range.end());
+        let mut grid = Self::default();
+        for ((x, y), value) in self.iter() {
+            let (x, y) = f(start, end, (*x, *y));
+            grid.insert(x, y, value.clone());
+        }
+        grid
+    }
+
+    fn one() -> K {
+        match 1_u8.try_into() {
+            Ok(one) => one,
+            _ => unreachable!("All 1_u8 values should be convertible to K"),
+        }
+    }
+}
+
+impl<K, D> Grid<K, D>
+where
+    K: Ord,
+{
+    /// Borrowed view of the cells within the ranges, cells keep their coordinates
+    pub fn window(
+        &self,
+        x_range: RangeInclusive<K>,
+        y_range: RangeInclusive<K>,
+    ) -> GridWindow<'_, K, D> {
+        GridWindow {
+            grid: &self.grid,
+            x_range,
+            y_range,
+        }
+    }
+}
+
+/// Borrowed view of a part of a grid, see [`Grid::window`]
+pub struct GridWindow<'g, K, D> {
+    grid: &'g BTreeMap<K, BTreeMap<K, D>>,
+    x_range: RangeInclusive<K>,
+    y_range: RangeInclusive<K>,
+}
+
+impl<'g, K, D> GridWindow<'g, K, D>
+where
+    K: Ord + Clone,
+{
+    pub fn contains_key(&self, x: K, y: K) -> bool {
+        self.get(x, y).is_some()
+    }
+    pub fn get(&self, x: K, y: K) -> Option<&'g D> {
+        if !self.x_range.contains(&x) || !self.y_range.contains(&y) {
+            return None;
+        }
+        self.grid.get(&y).and_then(|row| row.get(&x))
+    }
+    /// Cells in the window, none when a range starts after its end
+    pub fn iter(&self) -> impl Iterator<Item = ((&'g K, &'g K), &'g D)> + '_ {
+        // `BTreeMap::range` panics on inverted ranges
+        let rows = match self.x_range.is_empty() || self.y_range.is_empty() {
+            true => None,
+            false => Some(self.grid.range(self.y_range.clone())),
+        };
+        rows.into_iter().flatten().flat_map(|(y, row)| {
+            row.range(self.x_range.clone())
+                .map(move |(x, value)| ((x, y), value))
+        })
+    }
+    pub fn keys(&self) -> impl Iterator<Item = (&'g K, &'g K)> + '_ {
+        self.iter().map(|(c, _)| c)
+    }
+    pub fn values(&self) -> impl Iterator<Item = &'g D> + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+    /// Copy the cells of the window into a new grid
+    pub fn to_grid(&self) -> Grid<K, D>
+    where
+        D: Clone,
+    {
+        let mut grid = Grid::default();
+        for ((x, y), value) in self.iter() {
+            grid.insert(x.clone(), y.clone(), value.clone());
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_grid() -> Grid<usize, char> {
+        Grid::from("ABCDEF\nGHIJKL\nMNOPQR".to_string())
+    }
+
+    #[test]
+    fn should_rotate() {
+        let grid = create_grid();
+        assert_eq!(grid.rotate_cw().to_string(), "MGA\nNHB\nOIC\nPJD\nQKE\nRLF");
+        assert_eq!(
+            grid.rotate_ccw().to_string(),
+            "FLR\nEKQ\nDJP\nCIO\nBHN\nAGM"
+        );
+        assert_eq!(grid.rotate_cw().rotate_ccw().to_string(), grid.to_string());
+        assert_eq!(
+            grid.rotate_cw().rotate_cw().to_string(),
+            grid.flip_h().flip_v().to_string()
+        );
+    }
+
+    #[test]
+    fn should_flip() {
+        let grid = create_grid();
+        assert_eq!(grid.flip_h().to_string(), "FEDCBA\nLKJIHG\nRQPONM");
+        assert_eq!(grid.flip_v().to_string(), "MNOPQR\nGHIJKL\nABCDEF");
+        assert_eq!(grid.to_string(), "ABCDEF\nGHIJKL\nMNOPQR");
+    }
+
+    #[test]
+    fn should_crop_and_window() {
+        let grid = create_grid();
+        let cropped = grid.crop(1..=3, 1..=2);
+        assert_eq!(cropped.get(1, 1), Some(&'H'));
+        assert_eq!(cropped.get(0, 0), None);
+        assert_eq!(cropped.to_string(), "HIJ\nNOP");
+
+        let window = grid.window(4..=9, 0..=1);
+        assert_eq!(window.len(), 4);
+        assert_eq!(window.get(5, 1), Some(&'L'));
+        assert_eq!(window.get(3, 1), None);
+        assert_eq!(window.values().collect::<String>(), "EFKL");
+
+        // Inverted ranges, like a start computed past the end
+        let (low, high) = (1, 3);
+        assert!(grid.crop(high..=low, 0..=2).is_empty());
+        assert!(grid.window(0..=5, high..=low).is_empty());
+        assert_eq!(grid.window(high..=low, 0..=2).get(2, 1), None);
+    }
+
+    #[test]
+    fn should_pad() {
+        let grid: Grid<usize, char> = Grid::from("ab\ncd".to_string());
+        assert_eq!(grid.pad(1, '.').to_string(), "....\n.ab.\n.cd.\n....");
+        assert_eq!(grid.pad(1, '.').x_range(), Some(0..=3));
+    }
+
+    #[test]
+    fn should_tile() {
+        let grid: Grid<usize, char> = Grid::from("ab\ncd".to_string());
+        assert_eq!(
+            grid.tile(3, 2).to_string(),
+            "ababab\ncdcdcd\nababab\ncdcdcd"
+        );
+        assert_eq!(grid.tile(1, 1).to_string(), grid.to_string());
+        assert!(grid.tile(0, 2).is_empty());
+    }
+
+    /*
+    Mirrored fixture of to_diagonal:
+    ABCDEF
+    GHIJKL
+    MNOPQR
+
+      F
+     L E
+    R K D
+     Q J C
+      P I B
+       O H A
+        N G
+         M
+    */
+    #[test]
+    fn should_convert_to_anti_diagonal() {
+        let grid = create_grid();
+        assert_eq!(
+            grid.anti_diagonal().to_string(),
+            "  F     \n L E    \nR K D   \n Q J C  \n  P I B \n   O H A\n    N G \n     M  "
+        );
+        assert_eq!(grid.to_string(), "ABCDEF\nGHIJKL\nMNOPQR");
+    }
+
+    #[test]
+    fn should_match_to_diagonal_after_flip() {
+        let grid: Grid<usize, char> = Grid::from("abc\ndef\nghi".to_string());
+        let mut diagonal = grid.flip_h();
+        diagonal.to_diagonal();
+        assert_eq!(grid.anti_diagonal().to_string(), diagonal.to_string());
+        assert_eq!(
+            grid.anti_diagonal().to_string(),
+            "  c  \n f b \ni e a\n h d \n  g  "
+        );
+    }
+}
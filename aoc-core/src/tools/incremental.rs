@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{AStarBuilder, DisjointSet};
+
+type Coord = (usize, usize);
+
+/// A shortest path search which supports adding obstacles one at a time
+///
+/// The current path is only searched again when a new obstacle lands on it,
+/// all steps have a cost of 1.
+pub struct IncrementalSearch {
+    obstacles: HashSet<Coord>,
+    start: Coord,
+    end: Coord,
+    bounds: Option<(Coord, Coord)>,
+    path: Option<Vec<Coord>>,
+    path_cells: HashSet<Coord>,
+    searches: usize,
+}
+
+impl IncrementalSearch {
+    pub fn new(start: Coord, end: Coord) -> Self {
+        Self {
+            start,
+            end,
+            obstacles: HashSet::new(),
+            bounds: None,
+            path: None,
+            path_cells: HashSet::new(),
+            searches: 0,
+        }
+    }
+
+    /// Add initial obstacles to the grid, this can be called multiple times
+    pub fn with_obstacles(mut self, obstacles: HashSet<Coord>) -> Self {
+        self.obstacles.extend(obstacles);
+        self
+    }
+
+    /// Enforce search bounds, if not provided the bounds will be calculated
+    /// based on the start, end and initial obstacles
+    pub fn with_bounds(mut self, top_left: Coord, bottom_right: Coord) -> Self {
+        self.bounds = Some((top_left, bottom_right));
+        self
+    }
+
+    /// Current shortest path from start to end, including both
+    pub fn path(&mut self) -> Option<&Vec<Coord>> {
+        if self.searches == 0 {
+            self.search();
+        }
+        self.path.as_ref()
+    }
+
+    /// Amount of full searches done so far
+    pub fn searches(&self) -> usize {
+        self.searches
+    }
+
+    /// Add an obstacle, returns whether start and end are still connected
+    pub fn add_obstacle(&mut self, obstacle: Coord) -> bool {
+        if self.searches == 0 {
+            self.search();
+        }
+        if !self.obstacles.insert(obstacle) || self.path.is_none() {
+            return self.path.is_some();
+        }
+        if self.path_cells.contains(&obstacle) {
+            self.search();
+        }
+        self.path.is_some()
+    }
+
+    /// Find the first obstacle of the sequence after which the end can no
+    /// longer be reached, returns its index and coordinate
+    ///
+    /// Instead of searching after every insertion, all obstacles are placed
+    /// at once and removed again in reverse order until start and end connect.
+    pub fn first_blocking_obstacle<T: IntoIterator<Item = Coord>>(
+        &mut self,
+        obstacles: T,
+    ) -> Option<(usize, Coord)> {
+        let bounds = self.determine_bounds();
+        let mut first_seen = HashMap::new();
+        let mut covered = None;
+        let mut sequence = Vec::new();
+        for (i, c) in obstacles.into_iter().enumerate() {
+            if !in_bounds(bounds, c) || self.obstacles.contains(&c) {
+                continue;
+            }
+            if c == self.start || c == self.end {
+                // Covering start or end always disconnects, no need to check later obstacles
+                covered = Some((i, c));
+                break;
+            }
+            first_seen.entry(c).or_insert(i);
+            sequence.push((i, c));
+        }
+
+        let (top_left, bottom_right) = bounds;
+        let (start, end) = (self.start, self.end);
+        let mut sets = DisjointSet::new();
+        // Cells only join the sets through an open neighbour, start and end are
+        // needed even without one
+        sets.insert(start);
+        sets.insert(end);
+        let mut blocked = self.obstacles.clone();
+        blocked.extend(first_seen.keys());
+        let open_cell = |c: Coord, blocked: &HashSet<Coord>, sets: &mut DisjointSet<Coord>| {
+            for n in neighbors(bounds, c) {
+                if !blocked.contains(&n) {
+                    sets.union(c, n);
+                }
+            }
+        };
+        for y in top_left.1..=bottom_right.1 {
+            for x in top_left.0..=bottom_right.0 {
+                if !blocked.contains(&(x, y)) {
+                    open_cell((x, y), &blocked, &mut sets);
+                }
+            }
+        }
+        if sets.connected(&start, &end) {
+            return covered;
+        }
+        for (i, c) in sequence.into_iter().rev() {
+            if first_seen[&c] != i {
+                // Obstacle was placed before, it stays blocked
+                continue;
+            }
+            blocked.remove(&c);
+            open_cell(c, &blocked, &mut sets);
+            if sets.connected(&start, &end) {
+                return Some((i, c));
+            }
+        }
+        // Start and end were never connected
+        None
+    }
+
+    fn search(&mut self) {
+        let (top_left, bottom_right) = self.determine_bounds();
+        self.searches += 1;
+        self.path = AStarBuilder::new(self.start, self.end)
+            .with_obstacles(self.obstacles.clone())
+            .with_bounds(top_left, bottom_right)
+            .run()
+            .path();
+        self.path_cells = self.path.iter().flatten().copied().collect();
+    }
+
+    // Determine grid bounds if not defined
+    fn determine_bounds(&mut self) -> (Coord, Coord) {
+        if let Some(bounds) = self.bounds {
+            return bounds;
+        }
+        let mut top_left = min_coord(self.start, self.end);
+        let mut bottom_right = max_coord(self.start, self.end);
+        for c in self.obstacles.iter() {
+            top_left = min_coord(top_left, *c);
+            bottom_right = max_coord(bottom_right, *c);
+        }
+        self.bounds = Some((top_left, bottom_right));
+        (top_left, bottom_right)
+    }
+}
+
+fn min_coord(a: Coord, b: Coord) -> Coord {
+    (a.0.min(b.0), a.1.min(b.1))
+}
+fn max_coord(a: Coord, b: Coord) -> Coord {
+    (a.0.max(b.0), a.1.max(b.1))
+}
+fn in_bounds((top_left, bottom_right): (Coord, Coord), (x, y): Coord) -> bool {
+    x >= top_left.0 && x <= bottom_right.0 && y >= top_left.1 && y <= bottom_right.1
+}
+fn neighbors(bounds: (Coord, Coord), (x, y): Coord) -> impl Iterator<Item = Coord> {
+    [
+        (x.checked_sub(1), Some(y)),
+        (Some(x + 1), Some(y)),
+        (Some(x), y.checked_sub(1)),
+        (Some(x), Some(y + 1)),
+    ]
+    .into_iter()
+    .filter_map(|(nx, ny)| Some((nx?, ny?)))
+    .filter(move |c| in_bounds(bounds, *c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Test case:
+    S....
+    ###..
+    .....
+    E....
+    */
+    fn create_with_testcase() -> IncrementalSearch {
+        IncrementalSearch::new((0, 0), (0, 3))
+            .with_bounds((0, 0), (4, 3))
+            .with_obstacles(HashSet::from([(0, 1), (1, 1), (2, 1)]))
+    }
+
+    #[test]
+    fn test_add_obstacles() {
+        let mut search = create_with_testcase();
+        assert_eq!(search.path().map(|p| p.len()), Some(10));
+        assert!(search.add_obstacle((4, 1)));
+        assert!(!search.add_obstacle((3, 1)));
+        assert_eq!(search.path(), None);
+        assert!(!search.add_obstacle((3, 2)));
+        assert_eq!(search.searches(), 2);
+    }
+
+    #[test]
+    fn test_obstacle_off_path_does_not_search() {
+        let mut search = create_with_testcase();
+        assert!(search.add_obstacle((4, 0)));
+        assert!(search.add_obstacle((4, 3)));
+        assert_eq!(search.searches(), 1);
+    }
+
+    #[test]
+    fn test_first_blocking_obstacle() {
+        let mut search = create_with_testcase();
+        assert_eq!(
+            search.first_blocking_obstacle([(4, 0), (4, 0), (4, 1), (3, 1)]),
+            Some((3, (3, 1)))
+        );
+        assert_eq!(
+            search.first_blocking_obstacle([(3, 1), (4, 1), (3, 1)]),
+            Some((1, (4, 1)))
+        );
+        assert_eq!(search.first_blocking_obstacle([(4, 0), (4, 3)]), None);
+        assert_eq!(
+            search.first_blocking_obstacle([(1, 2), (0, 3), (3, 1), (4, 1)]),
+            Some((1, (0, 3)))
+        );
+    }
+
+    #[test]
+    fn test_first_blocking_obstacle_boxed_in() {
+        let mut search = IncrementalSearch::new((1, 0), (1, 0)).with_bounds((0, 0), (2, 0));
+        assert!(search.path().is_some());
+        assert_eq!(search.first_blocking_obstacle([(0, 0), (2, 0)]), None);
+        assert_eq!(
+            search.first_blocking_obstacle([(0, 0), (1, 0)]),
+            Some((1, (1, 0)))
+        );
+    }
+}
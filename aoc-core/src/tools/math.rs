@@ -0,0 +1,408 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// Greatest common divisor, always positive unless both values are 0
+pub fn gcd(a: i64, b: i64) -> i64 {
+    egcd(a, b).0
+}
+
+/// Least common multiple, `None` when the result does not fit in an i64
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(|v| v.abs())
+}
+
+/// Extended Euclidean algorithm, returns `(g, x, y)` with `a * x + b * y = g`
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1_i128, 0_i128);
+    let (mut old_y, mut y) = (0_i128, 1_i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    (old_r as i64, old_x as i64, old_y as i64)
+}
+
+/// Modular inverse of `a`, `None` when `a` and `m` are not coprime or `m` is not positive
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power `exp` modulo `m`, `None` when `m` is not positive
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    Some(result as i64)
+}
+
+/// Combine congruences `x = residue mod modulus` with the Chinese remainder theorem,
+/// returns the smallest non-negative `x` and the combined modulus
+///
+/// Moduli do not need to be coprime. Returns `None` when the congruences
+/// conflict, a modulus is not positive or the combined modulus does not fit
+/// in an i64.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0_i64, 1_i64), |(a1, n1), (a2, n2)| {
+            if *n2 <= 0 {
+                return None;
+            }
+            let (g, p, _) = egcd(n1, *n2);
+            let diff = *a2 as i128 - a1 as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            let n = lcm(n1, *n2)?;
+            let step = diff / g as i128 * p as i128 % (*n2 / g) as i128;
+            let x = (a1 as i128 + step * n1 as i128).rem_euclid(n as i128);
+            Some((x as i64, n))
+        })
+}
+
+/// Exact fraction, always stored in lowest terms with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    /// Create a fraction, `None` when the denominator is 0
+    pub fn new(num: i128, den: i128) -> Option<Self> {
+        (den != 0).then(|| Self::reduced(num, den))
+    }
+    // Only for denominators known to be non-zero
+    fn reduced(num: i128, den: i128) -> Self {
+        let g = gcd_i128(num, den);
+        let sign = if den < 0 { -1 } else { 1 };
+        Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+    /// Value as an integer, `None` when it has a fractional part
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+    /// Divide by `other`, `None` when it is 0
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        Self::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self { num: value, den: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::reduced(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+impl Sub for Rational {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+impl Mul for Rational {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::reduced(self.num * other.num, self.den * other.den)
+    }
+}
+impl Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Outcome of [`solve_linear`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSolution {
+    /// The equations contradict each other, or the rows do not all have one
+    /// coefficient per unknown and one right hand side
+    None,
+    /// Exactly one value for every unknown
+    Unique(Vec<Rational>),
+    /// The equations do not pin down every unknown
+    Infinite,
+}
+
+/// Determinant of a square matrix, calculated with Bareiss' fraction free elimination
+pub fn determinant(matrix: &[Vec<i128>]) -> i128 {
+    let n = matrix.len();
+    let mut m = matrix.to_vec();
+    let (pivots, sign) = bareiss(&mut m, n);
+    if pivots < n {
+        return 0;
+    }
+    match n {
+        0 => 1,
+        _ => sign * m[n - 1][n - 1],
+    }
+}
+
+/// Solve `a * x = b` exactly, square systems with a non-zero determinant use
+/// Cramer's rule, other systems are reduced with Bareiss' elimination
+///
+/// Intermediate values grow with the size of the system, this is meant for
+/// small systems only.
+pub fn solve_linear(a: &[Vec<i128>], b: &[i128]) -> LinearSolution {
+    let unknowns = a.first().map_or(0, |row| row.len());
+    if a.len() != b.len() || a.iter().any(|row| row.len() != unknowns) {
+        return LinearSolution::None;
+    }
+
+    if a.len() == unknowns {
+        let det = determinant(a);
+        if det != 0 {
+            let x = (0..unknowns)
+                .map(|i| {
+                    let replaced = a
+                        .iter()
+                        .zip(b)
+                        .map(|(row, b)| {
+                            let mut row = row.clone();
+                            row[i] = *b;
+                            row
+                        })
+                        .collect::<Vec<_>>();
+                    Rational::reduced(determinant(&replaced), det)
+                })
+                .collect();
+            return LinearSolution::Unique(x);
+        }
+    }
+
+    let mut m = a
+        .iter()
+        .zip(b)
+        .map(|(row, b)| {
+            let mut row = row.clone();
+            row.push(*b);
+            row
+        })
+        .collect::<Vec<_>>();
+    let (rank, _) = bareiss(&mut m, unknowns);
+    if m[rank..].iter().any(|row| row[unknowns] != 0) {
+        return LinearSolution::None;
+    }
+    if rank < unknowns {
+        return LinearSolution::Infinite;
+    }
+    // Every column has a pivot on the diagonal, substitute from the bottom up
+    let mut x = vec![Rational::from(0); unknowns];
+    for i in (0..unknowns).rev() {
+        let sum = (i + 1..unknowns).fold(Rational::from(m[i][unknowns]), |sum, j| {
+            sum - Rational::from(m[i][j]) * x[j]
+        });
+        // Pivots are never 0
+        x[i] = Rational::reduced(sum.num, sum.den * m[i][i]);
+    }
+    LinearSolution::Unique(x)
+}
+
+// Fraction free elimination of the first `columns` columns to row echelon form,
+// returns the amount of pivots and the sign change caused by row swaps
+fn bareiss(m: &mut [Vec<i128>], columns: usize) -> (usize, i128) {
+    let mut sign = 1;
+    let mut previous = 1;
+    let mut row = 0;
+    for col in 0..columns {
+        let pivot = match (row..m.len()).find(|r| m[*r][col] != 0) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        if pivot != row {
+            m.swap(pivot, row);
+            sign = -sign;
+        }
+        for i in row + 1..m.len() {
+            for j in col + 1..m[i].len() {
+                m[i][j] = (m[i][j] * m[row][col] - m[i][col] * m[row][j]) / previous;
+            }
+            m[i][col] = 0;
+        }
+        previous = m[row][col];
+        row += 1;
+    }
+    (row, sign)
+}
+
+fn gcd_i128(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs().max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(i64::MAX, 2), None);
+        let (g, x, y) = egcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+        assert_eq!(mod_inv(3, 0), None);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(3, 0, 1), Some(0));
+        assert_eq!(mod_pow(i64::MAX - 1, 3, i64::MAX), Some(i64::MAX - 1));
+        assert_eq!(mod_pow(2, 10, 0), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(0, 1 << 40), (1, (1 << 40) - 1)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 3), (0, 0)]), None);
+        assert_eq!(crt(&[(1, -3)]), None);
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, -4).unwrap();
+        assert_eq!(Some(half), Rational::new(-1, 2));
+        assert_eq!((half + Rational::from(1)).to_string(), "1/2");
+        assert_eq!(half * Rational::from(-2), Rational::from(1));
+        let quarter = Rational::new(1, 4).unwrap();
+        assert_eq!(
+            half.checked_div(quarter).and_then(|r| r.to_integer()),
+            Some(-2)
+        );
+        assert!(half < Rational::from(0));
+        assert_eq!(Rational::new(1, 0), None);
+        assert_eq!(half.checked_div(Rational::from(0)), None);
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(determinant(&[vec![3, 8], vec![4, 6]]), -14);
+        assert_eq!(
+            determinant(&[vec![0, 2, 1], vec![1, 0, 0], vec![2, 1, 3]]),
+            -5
+        );
+        assert_eq!(determinant(&[vec![1, 2], vec![2, 4]]), 0);
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        assert_eq!(
+            solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400]),
+            LinearSolution::Unique(vec![Rational::from(80), Rational::from(40)])
+        );
+        assert_eq!(
+            solve_linear(&[vec![2, 0], vec![0, 3]], &[1, 1]),
+            LinearSolution::Unique(vec![Rational::reduced(1, 2), Rational::reduced(1, 3)])
+        );
+        assert_eq!(
+            solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]),
+            LinearSolution::Infinite
+        );
+        assert_eq!(
+            solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 7]),
+            LinearSolution::None
+        );
+        // More equations than unknowns
+        assert_eq!(
+            solve_linear(&[vec![1, 1], vec![1, -1], vec![2, 0]], &[4, 2, 6]),
+            LinearSolution::Unique(vec![Rational::from(3), Rational::from(1)])
+        );
+        assert_eq!(
+            solve_linear(&[vec![1, 1], vec![1, -1], vec![2, 0]], &[4, 2, 7]),
+            LinearSolution::None
+        );
+        // Malformed systems
+        assert_eq!(
+            solve_linear(&[vec![1, 1], vec![1, -1]], &[4]),
+            LinearSolution::None
+        );
+        assert_eq!(
+            solve_linear(&[vec![1, 1], vec![1]], &[4, 2]),
+            LinearSolution::None
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::{borrow::Borrow, collections::HashMap, fmt::Display, hash::Hash};
+
+/// Cache for the results of a recursive function
+///
+/// The cache lives as long as the `Memo`, create one per run so repeated runs
+/// (like benchmark iterations) do not share results.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+/// Hit and miss counts of a [`Memo`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+impl MemoStats {
+    /// Fraction of lookups answered from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.entries,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached value for the key, or compute and store it with `compute`
+    ///
+    /// `compute` receives the memo so it can recurse through it. The key is only
+    /// converted to an owned value on a miss, so `&[T]` can be used for `Vec<T>` keys.
+    pub fn get_or_compute<Q, F>(&mut self, key: &Q, compute: F) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+    /// Drop all cached values and reset the stats
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Recursive function with its own [`Memo`]
+///
+/// The function receives a callback for the recursive calls, which go through
+/// the cache as well.
+///
+/// ```
+/// use aoc_core::tools::MemoFn;
+///
+/// let mut fib = MemoFn::new(|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+/// assert_eq!(fib.call(90), 2880067194370816120);
+/// assert_eq!(fib.stats().misses, 91);
+/// ```
+pub struct MemoFn<K, V, F> {
+    memo: Memo<K, V>,
+    function: F,
+}
+
+impl<K, V, F> MemoFn<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(function: F) -> Self {
+        Self {
+            memo: Memo::new(),
+            function,
+        }
+    }
+    pub fn call(&mut self, key: K) -> V {
+        call_memoized(&mut self.memo, &self.function, key)
+    }
+    pub fn stats(&self) -> MemoStats {
+        self.memo.stats()
+    }
+    /// Drop all cached values and reset the stats
+    pub fn clear(&mut self) {
+        self.memo.clear();
+    }
+}
+
+fn call_memoized<K, V, F>(memo: &mut Memo<K, V>, function: &F, key: K) -> V
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    memo.get_or_compute(&key.clone(), |memo| {
+        function(&mut |key| call_memoized(memo, function, key), key)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn count_splits(memo: &mut Memo<Vec<u8>, usize>, word: &[u8]) -> usize {
+        if word.is_empty() {
+            return 1;
+        }
+        memo.get_or_compute(word, |memo| {
+            (1..=word.len().min(2))
+                .map(|len| count_splits(memo, &word[len..]))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn should_cache_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(count_splits(&mut memo, b"abcdef"), 13);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 4,
+                misses: 6,
+                entries: 6
+            }
+        );
+        assert_eq!(count_splits(&mut memo, b"abcdef"), 13);
+        assert_eq!(memo.stats().hits, 5);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), MemoStats::default());
+    }
+
+    #[test]
+    fn should_memoize_function() {
+        let mut fib = MemoFn::new(|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+        assert_eq!(fib.call(10), 55);
+        assert_eq!(fib.stats().misses, 11);
+        assert_eq!(fib.stats().hits, 8);
+        assert_eq!(fib.call(11), 89);
+        assert_eq!(fib.stats().misses, 12);
+
+        fib.clear();
+        assert_eq!(fib.stats().entries, 0);
+        assert_eq!(
+            fib.stats().to_string(),
+            "0 hits, 0 misses, 0 entries (0.0% hit rate)"
+        );
+    }
+}
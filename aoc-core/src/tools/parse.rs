@@ -0,0 +1,480 @@
+//! Parser combinators for puzzle inputs
+//!
+//! A parser is a function from the input and a byte offset to the parsed value,
+//! the offset after it and the furthest failure it recovered from, so closures
+//! with that signature are parsers as well. Failures keep their offset,
+//! [`Parser::parse`] turns it into a line and column.
+//!
+//! ```
+//! use aoc_core::tools::parse::*;
+//!
+//! let button = literal("Button A: X+")
+//!     .ignore_then(uint::<u32>())
+//!     .then_ignore(literal(", Y+"))
+//!     .then(uint::<u32>());
+//! assert_eq!(button.parse("Button A: X+94, Y+34"), Ok((94, 34)));
+//!
+//! let error = button.parse("Button A: X+94, Y34").unwrap_err();
+//! assert_eq!(error.to_string(), "line 1, column 15: expected ', Y+', found ', Y34'");
+//! ```
+
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+/// Failed parse at a byte offset of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub pos: usize,
+    pub expected: String,
+}
+
+impl Failure {
+    pub fn new(pos: usize, expected: impl Into<String>) -> Self {
+        Self {
+            pos,
+            expected: expected.into(),
+        }
+    }
+
+    // Keep the failure which got furthest, both are listed when they fail at the same offset
+    fn merge(self, other: Self) -> Self {
+        match self.pos.cmp(&other.pos) {
+            std::cmp::Ordering::Less => other,
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Equal => Failure {
+                pos: self.pos,
+                expected: format!("{} or {}", self.expected, other.expected),
+            },
+        }
+    }
+
+    // Report a recovered failure instead when it got further
+    fn or_recovered(self, recovered: Option<Failure>) -> Self {
+        match recovered {
+            Some(recovered) if recovered.pos > self.pos => recovered,
+            _ => self,
+        }
+    }
+}
+
+// Keep the recovered failure which got furthest, the first one on a tie
+fn furthest(a: Option<Failure>, b: Option<Failure>) -> Option<Failure> {
+    match (a, b) {
+        (Some(a), Some(b)) if b.pos > a.pos => Some(b),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Parsed value, the offset after it and the furthest failure a list in it
+/// recovered from, see [`Parser::separated_by`]
+pub type ParseResult<T> = Result<(T, usize, Option<Failure>), Failure>;
+
+/// Failed parse with the position in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the failure, starting at 1
+    pub line: usize,
+    /// Character in the line, starting at 1
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    fn new(input: &str, failure: Failure) -> Self {
+        let before = &input[..failure.pos];
+        let rest = input[failure.pos..].lines().next().unwrap_or_default();
+        let found = if failure.pos == input.len() {
+            "end of input".to_string()
+        } else if rest.is_empty() {
+            "end of line".to_string()
+        } else {
+            format!("'{}'", rest.chars().take(20).collect::<String>())
+        };
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before.chars().rev().take_while(|c| *c != '\n').count() + 1,
+            expected: failure.expected,
+            found,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub trait Parser<T> {
+    /// Parse the value starting at byte offset `pos`
+    fn parse_at(&self, input: &str, pos: usize) -> ParseResult<T>;
+
+    /// Parse the whole input, only trailing whitespace may remain
+    ///
+    /// When a list stopped at a value which failed further along than the
+    /// parse, the failure of that value is reported.
+    fn parse(&self, input: &str) -> Result<T, ParseError> {
+        self.parse_at(input, 0)
+            .and_then(
+                |(value, pos, recovered)| match input[pos..].trim().is_empty() {
+                    true => Ok(value),
+                    false => Err(Failure::new(pos, "end of input").or_recovered(recovered)),
+                },
+            )
+            .map_err(|failure| ParseError::new(input, failure))
+    }
+
+    /// Every match in the input, characters which do not start a match are skipped
+    fn scan<'a>(&'a self, input: &'a str) -> Scanner<'a, Self, T>
+    where
+        Self: Sized,
+    {
+        Scanner {
+            parser: self,
+            input,
+            pos: 0,
+            value: PhantomData,
+        }
+    }
+
+    fn map<U, F>(self, f: F) -> impl Parser<U>
+    where
+        Self: Sized,
+        F: Fn(T) -> U,
+    {
+        move |input: &str, pos: usize| {
+            self.parse_at(input, pos)
+                .map(|(v, pos, recovered)| (f(v), pos, recovered))
+        }
+    }
+
+    /// Convert the value, an error fails the parse at the start of the value
+    fn try_map<U, F>(self, f: F) -> impl Parser<U>
+    where
+        Self: Sized,
+        F: Fn(T) -> Result<U, String>,
+    {
+        move |input: &str, pos: usize| {
+            let (value, next, recovered) = self.parse_at(input, pos)?;
+            match f(value) {
+                Ok(value) => Ok((value, next, recovered)),
+                Err(e) => Err(Failure::new(pos, e).or_recovered(recovered)),
+            }
+        }
+    }
+
+    /// Both values in sequence
+    fn then<U, P>(self, next: P) -> impl Parser<(T, U)>
+    where
+        Self: Sized,
+        P: Parser<U>,
+    {
+        move |input: &str, pos: usize| {
+            let (a, pos, recovered) = self.parse_at(input, pos)?;
+            match next.parse_at(input, pos) {
+                Ok((b, pos, next)) => Ok(((a, b), pos, furthest(recovered, next))),
+                Err(failure) => Err(failure.or_recovered(recovered)),
+            }
+        }
+    }
+
+    /// Parse `next` after this value and drop its result
+    fn then_ignore<U, P>(self, next: P) -> impl Parser<T>
+    where
+        Self: Sized,
+        P: Parser<U>,
+    {
+        self.then(next).map(|(a, _)| a)
+    }
+
+    /// Parse `next` after this value and keep only its result
+    fn ignore_then<U, P>(self, next: P) -> impl Parser<U>
+    where
+        Self: Sized,
+        P: Parser<U>,
+    {
+        self.then(next).map(|(_, b)| b)
+    }
+
+    /// Try `other` when this parser fails
+    fn or<P>(self, other: P) -> impl Parser<T>
+    where
+        Self: Sized,
+        P: Parser<T>,
+    {
+        move |input: &str, pos: usize| {
+            self.parse_at(input, pos)
+                .or_else(|a| other.parse_at(input, pos).map_err(|b| a.merge(b)))
+        }
+    }
+
+    fn optional(self) -> impl Parser<Option<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &str, pos: usize| match self.parse_at(input, pos) {
+            Ok((value, pos, recovered)) => Ok((Some(value), pos, recovered)),
+            Err(_) => Ok((None, pos, None)),
+        }
+    }
+
+    /// Zero or more values with separators in between
+    ///
+    /// The list ends when a value fails at its start, a value which fails
+    /// halfway fails the list. A value failing after a separator ends the list
+    /// before the separator, its failure is kept in the result and reported
+    /// when the parse fails before it, see [`Parser::parse`].
+    fn separated_by<S, P>(self, separator: P) -> impl Parser<Vec<T>>
+    where
+        Self: Sized,
+        P: Parser<S>,
+    {
+        move |input: &str, pos: usize| {
+            let mut values = vec![];
+            let mut end = pos;
+            let mut recovered = None;
+            loop {
+                let (start, separated) = match values.is_empty() {
+                    true => (end, None),
+                    false => match separator.parse_at(input, end) {
+                        Ok((_, next, separated)) => (next, separated),
+                        Err(_) => break,
+                    },
+                };
+                match self.parse_at(input, start) {
+                    Ok((value, next, value_recovered)) => {
+                        values.push(value);
+                        end = next;
+                        recovered = furthest(furthest(recovered, separated), value_recovered);
+                    }
+                    Err(failure) if failure.pos > start => {
+                        return Err(failure.or_recovered(furthest(recovered, separated)))
+                    }
+                    Err(failure) => {
+                        if !values.is_empty() {
+                            recovered = furthest(recovered, Some(failure));
+                        }
+                        break;
+                    }
+                }
+            }
+            Ok((values, end, recovered))
+        }
+    }
+
+    /// Name the expected value in errors which occur at its start
+    fn label(self, expected: &'static str) -> impl Parser<T>
+    where
+        Self: Sized,
+    {
+        move |input: &str, pos: usize| {
+            self.parse_at(input, pos).map_err(|f| match f.pos == pos {
+                true => Failure::new(pos, expected),
+                false => f,
+            })
+        }
+    }
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&str, usize) -> ParseResult<T>,
+{
+    fn parse_at(&self, input: &str, pos: usize) -> ParseResult<T> {
+        self(input, pos)
+    }
+}
+
+/// Iterator over the matches of a parser, see [`Parser::scan`]
+pub struct Scanner<'a, P, T> {
+    parser: &'a P,
+    input: &'a str,
+    pos: usize,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<P: Parser<T>, T> Iterator for Scanner<'_, P, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while let Some(c) = self.input[self.pos..].chars().next() {
+            match self.parser.parse_at(self.input, self.pos) {
+                Ok((value, next, _)) if next > self.pos => {
+                    self.pos = next;
+                    return Some(value);
+                }
+                _ => self.pos += c.len_utf8(),
+            }
+        }
+        None
+    }
+}
+
+/// Exact text
+pub fn literal(text: &'static str) -> impl Parser<&'static str> {
+    move |input: &str, pos: usize| match input[pos..].starts_with(text) {
+        true => Ok((text, pos + text.len(), None)),
+        false => Err(Failure::new(pos, format!("'{}'", text.escape_debug()))),
+    }
+}
+
+/// One or more characters matching the predicate
+pub fn take_while<F>(expected: &'static str, predicate: F) -> impl Parser<String>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: &str, pos: usize| {
+        let len = input[pos..]
+            .find(|c| !predicate(c))
+            .unwrap_or(input.len() - pos);
+        match len {
+            0 => Err(Failure::new(pos, expected)),
+            len => Ok((input[pos..pos + len].to_string(), pos + len, None)),
+        }
+    }
+}
+
+/// Unsigned integer in decimal notation
+pub fn uint<T: FromStr>() -> impl Parser<T> {
+    take_while("integer", |c| c.is_ascii_digit()).try_map(|digits| {
+        digits
+            .parse()
+            .map_err(|_| format!("integer in range, got {digits}"))
+    })
+}
+
+/// Integer in decimal notation with an optional sign
+pub fn int<T: FromStr>() -> impl Parser<T> {
+    literal("-")
+        .or(literal("+"))
+        .optional()
+        .then(take_while("integer", |c| c.is_ascii_digit()))
+        .label("integer")
+        .try_map(|(sign, digits)| {
+            let text = format!("{}{digits}", sign.unwrap_or_default());
+            text.parse()
+                .map_err(|_| format!("integer in range, got {text}"))
+        })
+}
+
+/// Letters, digits and underscores
+pub fn identifier() -> impl Parser<String> {
+    take_while("identifier", |c| c.is_alphanumeric() || c == '_')
+}
+
+/// Zero or more spaces or tabs
+pub fn spaces() -> impl Parser<()> {
+    |input: &str, pos: usize| {
+        let len = input[pos..]
+            .find(|c| c != ' ' && c != '\t')
+            .unwrap_or(input.len() - pos);
+        Ok(((), pos + len, None))
+    }
+}
+
+pub fn newline() -> impl Parser<&'static str> {
+    literal("\n").label("newline")
+}
+
+/// Empty line between two sections of the input
+pub fn blank_line() -> impl Parser<&'static str> {
+    literal("\n\n").label("blank line")
+}
+
+/// One value per line
+pub fn lines<T>(parser: impl Parser<T>) -> impl Parser<Vec<T>> {
+    parser.separated_by(newline())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_sequences() {
+        let pair = int::<i32>().then_ignore(literal(",")).then(int::<i32>());
+        assert_eq!(pair.parse("3,-4"), Ok((3, -4)));
+        assert_eq!(pair.parse("3,+4\n"), Ok((3, 4)));
+        assert_eq!(
+            uint::<u8>().parse("300").unwrap_err().expected,
+            "integer in range, got 300"
+        );
+
+        let list = lines(uint::<u32>().separated_by(literal(",")));
+        assert_eq!(list.parse("1,2\n3"), Ok(vec![vec![1, 2], vec![3]]));
+        let sections = lines(identifier())
+            .then_ignore(blank_line())
+            .then(lines(identifier()));
+        assert_eq!(
+            sections.parse("a\nb\n\nc"),
+            Ok((
+                vec!["a".to_string(), "b".to_string()],
+                vec!["c".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_position() {
+        let operator = literal("AND").or(literal("OR")).or(literal("XOR"));
+        let gate = identifier()
+            .then_ignore(spaces())
+            .then(operator)
+            .then_ignore(spaces())
+            .then(identifier());
+        let error = lines(gate).parse("x AND y\nx NAND y").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected 'AND' or 'OR' or 'XOR', found 'NAND y'"
+        );
+
+        let error = lines(uint::<u32>()).parse("1\n2\nx").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected integer, found 'x'"
+        );
+        let error = lines(uint::<u32>())
+            .then_ignore(literal("end"))
+            .parse("1\n2\nx")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        // Recovered failures of nested lists are carried out of the outer list
+        let error = lines(uint::<u32>().separated_by(literal(",")))
+            .then_ignore(literal("end"))
+            .parse("1,2\n3,x")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(lines(uint::<u32>()).parse("1\n2\n"), Ok(vec![1, 2]));
+        let error = literal("a").then(literal("b")).parse("a").unwrap_err();
+        assert_eq!(error.found, "end of input");
+        assert_eq!(
+            uint::<u32>()
+                .label("count")
+                .parse("x")
+                .unwrap_err()
+                .expected,
+            "count"
+        );
+    }
+
+    #[test]
+    fn should_scan_matches() {
+        let mul = literal("mul(")
+            .ignore_then(uint::<u32>())
+            .then_ignore(literal(","))
+            .then(uint::<u32>())
+            .then_ignore(literal(")"));
+        let found = mul
+            .scan("xmul(2,4)%mul[3,7]mul(mul(11,8)é")
+            .collect::<Vec<_>>();
+        assert_eq!(found, [(2, 4), (11, 8)]);
+        assert_eq!(mul.scan("").count(), 0);
+    }
+}
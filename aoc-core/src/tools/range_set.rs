@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, ops::Range, ops::Sub};
+
+/// Set of values stored as disjoint, non-adjacent half-open ranges
+///
+/// Overlapping and touching ranges are merged on insert, removing part of a
+/// range splits it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    // Start to end of every range
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> RangeSet<T>
+where
+    T: Ord + Copy + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the range, merging it with overlapping and adjacent ranges
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(e);
+            }
+        }
+        let merged = self
+            .ranges
+            .range(start..=end)
+            .map(|(s, _)| *s)
+            .collect::<Vec<_>>();
+        for s in merged {
+            end = end.max(self.ranges.remove(&s).unwrap());
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Remove the range, splitting ranges which extend past it
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.ranges.insert(s, range.start);
+                if e > range.end {
+                    self.ranges.insert(range.end, e);
+                }
+            }
+        }
+        let removed = self
+            .ranges
+            .range(range.start..range.end)
+            .map(|(s, _)| *s)
+            .collect::<Vec<_>>();
+        for s in removed {
+            let e = self.ranges.remove(&s).unwrap();
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+            }
+        }
+    }
+
+    /// Add all ranges of the other set
+    pub fn merge(&mut self, other: &Self) {
+        for range in other.iter() {
+            self.insert(range);
+        }
+    }
+
+    /// Move all values from `at` onwards to a new set, like [`BTreeMap::split_off`]
+    pub fn split_off(&mut self, at: T) -> Self {
+        let mut tail = self.ranges.split_off(&at);
+        if let Some((&s, &e)) = self.ranges.iter().next_back() {
+            if e > at {
+                self.ranges.insert(s, at);
+                tail.insert(at, e);
+            }
+        }
+        Self { ranges: tail }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, e)| value < *e)
+    }
+
+    /// First range containing at least `size` values
+    pub fn first_span(&self, size: T) -> Option<Range<T>> {
+        self.iter().find(|r| r.end - r.start >= size)
+    }
+
+    /// Values within the bounds which are not in the set, as ranges
+    pub fn gaps(&self, bounds: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let (mut cursor, end) = (bounds.start, bounds.end.max(bounds.start));
+        // Include the range overlapping the start of the bounds
+        let from = self
+            .ranges
+            .range(..=cursor)
+            .next_back()
+            .map_or(cursor, |(s, _)| *s);
+        let mut ranges = self.ranges.range(from..end);
+        std::iter::from_fn(move || {
+            while cursor < end {
+                let (s, e) = ranges.next().map_or((end, end), |(s, e)| (*s, *e));
+                let gap = cursor..s.min(end);
+                cursor = cursor.max(e);
+                if gap.start < gap.end {
+                    return Some(gap);
+                }
+            }
+            None
+        })
+    }
+
+    /// First gap within the bounds of at least `size` values
+    pub fn first_gap(&self, bounds: Range<T>, size: T) -> Option<Range<T>> {
+        self.gaps(bounds).find(|r| r.end - r.start >= size)
+    }
+
+    /// Ranges in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(s, e)| *s..*e)
+    }
+
+    /// Number of disjoint ranges
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl<T> FromIterator<Range<T>> for RangeSet<T>
+where
+    T: Ord + Copy + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ranges(set: &RangeSet<i32>) -> Vec<Range<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn should_merge_on_insert() {
+        let mut set = RangeSet::from_iter([0..3, 5..7, 10..12]);
+        assert_eq!(ranges(&set), [0..3, 5..7, 10..12]);
+        set.insert(3..4);
+        set.insert(6..10);
+        assert_eq!(ranges(&set), [0..4, 5..12]);
+        set.insert(-2..20);
+        assert_eq!((set.len(), set.first_span(0)), (1, Some(-2..20)));
+        set.insert(4..4);
+        assert_eq!(set.len(), 1);
+
+        let mut other = RangeSet::from_iter([20..22, 30..31]);
+        other.merge(&set);
+        assert_eq!(ranges(&other), [-2..22, 30..31]);
+    }
+
+    #[test]
+    fn should_split_on_remove() {
+        let mut set = RangeSet::from_iter([0..10, 12..15]);
+        set.remove(3..5);
+        assert_eq!(ranges(&set), [0..3, 5..10, 12..15]);
+        set.remove(8..13);
+        assert_eq!(ranges(&set), [0..3, 5..8, 13..15]);
+        set.remove(-5..6);
+        assert_eq!(ranges(&set), [6..8, 13..15]);
+        assert!(set.contains(7));
+        assert!(!set.contains(8));
+        assert!(!set.contains(0));
+
+        let tail = set.split_off(7);
+        assert_eq!((set.len(), set.first_span(0)), (1, Some(6..7)));
+        assert_eq!(ranges(&tail), [7..8, 13..15]);
+    }
+
+    #[test]
+    fn should_find_spans_and_gaps() {
+        let set = RangeSet::from_iter([0..2, 4..5, 8..12, 20..21]);
+        assert_eq!(set.first_span(3), Some(8..12));
+        assert_eq!(set.first_span(5), None);
+        assert_eq!(
+            set.gaps(1..25).collect::<Vec<_>>(),
+            [2..4, 5..8, 12..20, 21..25]
+        );
+        assert_eq!(set.gaps(9..11).count(), 0);
+        assert_eq!(set.first_gap(0..25, 3), Some(5..8));
+        assert_eq!(set.first_gap(0..15, 4), None);
+        assert_eq!(RangeSet::new().first_gap(3..8, 5), Some(3..8));
+    }
+}
@@ -6,15 +6,21 @@ use proc_macro2::TokenStream;
 mod attributes;
 
 use attributes::AocAttributes;
+pub use attributes::FetchErrors;
 
 lazy_static::lazy_static! {
     static ref AOC_YEAR: Mutex<Option<u32>> = Mutex::new(None);
+    static ref FETCH_ERRORS: Mutex<FetchErrors> = Mutex::new(FetchErrors::default());
 }
 
 pub fn get_year() -> Option<u32> {
     *AOC_YEAR.lock().unwrap()
 }
 
+pub fn get_fetch_errors() -> FetchErrors {
+    *FETCH_ERRORS.lock().unwrap()
+}
+
 pub(crate) fn aoc_impl(args: TokenStream, input: TokenStream) -> TokenStream {
     let attr_args = match NestedMeta::parse_meta_list(args) {
         Ok(v) => v,
@@ -27,6 +33,7 @@ pub(crate) fn aoc_impl(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let mut val = AOC_YEAR.lock().unwrap();
     *val = Some(args.year);
+    *FETCH_ERRORS.lock().unwrap() = args.fetch_errors;
 
     input
 }
@@ -3,4 +3,20 @@ use darling::FromMeta;
 #[derive(Debug, FromMeta)]
 pub struct AocAttributes {
     pub year: u32,
+    /// What to do when the puzzle text of a day could not be fetched
+    #[darling(default)]
+    pub fetch_errors: FetchErrors,
+}
+
+/// `#[aoc(year = 2024, fetch_errors = "warn")]`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub enum FetchErrors {
+    /// Leave the puzzle text out of the docs
+    #[default]
+    Ignore,
+    /// Emit a compiler warning
+    Warn,
+    /// Fail the build
+    Error,
 }
@@ -1,14 +1,19 @@
 use darling::{ast::NestedMeta, FromMeta};
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{parse_quote, spanned::Spanned};
 
 use aoc_procmacro_internals::{get_aoc_data, AocDataType};
 
-use super::aoc::get_year;
+use super::aoc::{get_fetch_errors, get_year, FetchErrors};
 
 mod attributes;
+mod examples;
+mod params;
 
 use attributes::{validate_day, AocAttributes};
+use examples::example_tests;
+use params::params_impl;
 
 const MISSING_DAY_ERROR: &str =
     "Could not determine puzzle day. Use one of these methods to define the day:
@@ -17,42 +22,100 @@ const MISSING_DAY_ERROR: &str =
 ";
 
 pub(crate) fn aoc_puzzle_impl(args: TokenStream, input: TokenStream) -> TokenStream {
-    let input: syn::ItemStruct = match syn::parse2::<syn::ItemStruct>(input) {
-        Ok(is) => is,
+    let mut input: syn::Item = match syn::parse2::<syn::Item>(input) {
+        Ok(item) => item,
         Err(e) => return darling::Error::from(e).write_errors(),
     };
-    let attr_args = match NestedMeta::parse_meta_list(args) {
-        Ok(v) => v,
-        Err(e) => return darling::Error::from(e).write_errors(),
+    match expand(args, &mut input) {
+        Ok(expanded) => expanded,
+        Err(e) => {
+            // Keep the item, so its impls do not add errors of their own
+            let errors = e.write_errors();
+            quote!(#errors #input)
+        }
+    }
+}
+
+fn expand(args: TokenStream, input: &mut syn::Item) -> Result<TokenStream, darling::Error> {
+    // Strip the `#[param]` attributes first, the item is kept when expanding fails
+    let params = match input {
+        syn::Item::Struct(item) => {
+            params_impl(&item.ident, &item.generics, Some(&mut item.fields))?
+        }
+        syn::Item::Enum(item) => params_impl(&item.ident, &item.generics, None)?,
+        item => {
+            return Err(syn::Error::new_spanned(
+                item,
+                "`aoc_puzzle` can only be used on a struct or an enum",
+            )
+            .into())
+        }
     };
-    let args = match AocAttributes::from_list(&attr_args) {
-        Ok(v) => v,
-        Err(e) => return e.write_errors(),
+    let (ident, generics) = match &*input {
+        syn::Item::Struct(item) => (&item.ident, &item.generics),
+        syn::Item::Enum(item) => (&item.ident, &item.generics),
+        _ => unreachable!(),
     };
-
-    let (aoc_day, aoc_year) = match extract_day_year(&input, args.day) {
-        Ok(dy) => dy,
-        Err(e) => return e.write_errors(),
+    let args = AocAttributes::from_list(&NestedMeta::parse_meta_list(args)?)?;
+    let (aoc_day, aoc_year) = extract_day_year(ident, args.day.as_deref().copied())?;
+
+    let (puzzle_description, fetch_error) = match get_aoc_data(AocDataType::Text, aoc_day, aoc_year)
+    {
+        Ok(description) => (Some(description), TokenStream::new()),
+        Err(e) => {
+            let message = format!(
+                "Failed to get puzzle description for day {} ({}): {}",
+                aoc_day, aoc_year, e
+            );
+            (None, fetch_error(&message))
+        }
     };
 
-    let puzzle_description = match get_aoc_data(AocDataType::Text, aoc_day, aoc_year) {
-        Ok(description) => description,
-        Err(e) => format!(
-            "Failed to get puzzle description for day {} ({}): {}",
-            aoc_day, aoc_year, e
-        ),
+    let solution = match &args.constructor {
+        Some(constructor) => quote_spanned!(constructor.span()=> #constructor()),
+        // Point at the type when it does not implement `Default`
+        None => quote_spanned!(ident.span()=> <#ident as ::core::default::Default>::default()),
+    };
+    let tests = example_tests(ident, &solution, args)?;
+    // The puzzle text is left out of the docs when it could not be fetched
+    let doc = puzzle_description.map(|description| {
+        let doc_text = description.replace("```", "```text");
+        quote!(#[doc = #doc_text])
+    });
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let where_clause = if generics.params.is_empty() {
+        where_clause.cloned()
+    } else {
+        let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+        where_clause.predicates.push(parse_quote! {
+            #ident #ty_generics: aoc_core::ParsedPuzzleSolution + aoc_core::PuzzleParams + 'static
+        });
+        Some(where_clause)
     };
 
-    let ident = &input.ident;
-    let doc_text = puzzle_description.replace("```", "```text");
+    Ok(quote! {
+        #fetch_error
 
-    let expanded = quote! {
-        #[doc = #doc_text]
+        #doc
         #input
 
+        #params
+
+        /// Day of the solution, `setup_solutions!` checks that every day is unique
+        pub const DAY: u32 = #aoc_day;
+
         pub fn register_solution(solutions: &mut aoc_core::SolutionCollection) {
+            register_solution_with(solutions, #solution);
+        }
+
+        /// Register a configured instance of the solution
+        pub fn register_solution_with #impl_generics(
+            solutions: &mut aoc_core::SolutionCollection,
+            solution: #ident #ty_generics,
+        ) #where_clause {
             let wrapper = aoc_core::SolutionWrapper::new(
-                #ident::default(),
+                solution,
                 aoc_core::SolutionProps {
                     day: #aoc_day,
                     year: #aoc_year,
@@ -61,26 +124,43 @@ pub(crate) fn aoc_puzzle_impl(args: TokenStream, input: TokenStream) -> TokenStr
 
             solutions.register_solution(Box::new(wrapper));
         }
-    };
 
-    expanded
+        #tests
+    })
 }
 
 fn extract_day_year(
-    input: &syn::ItemStruct,
+    ident: &syn::Ident,
     day_from_arg: Option<u32>,
 ) -> Result<(u32, u32), darling::Error> {
     let aoc_year = get_aoc_year()?;
-    let aoc_day = match validate_day(day_from_arg.or_else(|| get_day_from_name(input)))? {
-        Some(day) => day,
-        None => return Err(darling::Error::custom(MISSING_DAY_ERROR)),
+    let aoc_day = match (day_from_arg, get_day_from_name(ident)) {
+        (Some(day), _) => day,
+        (None, Some(day)) => validate_day(day, ident.span())?,
+        (None, None) => return Err(syn::Error::new(ident.span(), MISSING_DAY_ERROR).into()),
     };
     Ok((aoc_day, aoc_year))
 }
 
+/// Report a failed puzzle fetch as configured with `#[aoc(fetch_errors = ...)]`
+fn fetch_error(message: &str) -> TokenStream {
+    match get_fetch_errors() {
+        FetchErrors::Ignore => TokenStream::new(),
+        // There is no stable way to emit a warning, use of a deprecated item is
+        FetchErrors::Warn => quote! {
+            const _: () = {
+                #[deprecated(note = #message)]
+                struct PuzzleNotFetched;
+                let _ = PuzzleNotFetched;
+            };
+        },
+        FetchErrors::Error => syn::Error::new(Span::call_site(), message).to_compile_error(),
+    }
+}
+
 // Attempt to parse the day from the suffix in the name of the struct
-fn get_day_from_name(input: &syn::ItemStruct) -> Option<u32> {
-    let name = input.ident.to_string();
+fn get_day_from_name(ident: &syn::Ident) -> Option<u32> {
+    let name = ident.to_string();
     // Reverse the string, take all the digits from the end, reverse it back and parse it as u32
     let day = name
         .chars()
@@ -100,8 +180,11 @@ fn get_day_from_name(input: &syn::ItemStruct) -> Option<u32> {
 fn get_aoc_year() -> Result<u32, darling::Error> {
     match get_year() {
         Some(year) => Ok(year),
-        _ => Err(darling::Error::custom(
-            "AOC year not set, call `aoc_core::set_year()`".to_string(),
-        )),
+        _ => Err(syn::Error::new(
+            Span::call_site(),
+            "AOC year not set, add `#[aoc(year = 2024)]` to a function in the crate root, \
+             like `main`",
+        )
+        .into()),
     }
 }
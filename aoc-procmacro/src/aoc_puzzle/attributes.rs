@@ -1,23 +1,112 @@
-use darling::FromMeta;
+use std::collections::HashMap;
+
+use darling::{util::SpannedValue, FromMeta};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
 #[derive(Debug, FromMeta)]
 #[darling(and_then = AocAttributes::autocorrect)]
 pub struct AocAttributes {
-    pub day: Option<u32>,
+    pub day: Option<SpannedValue<u32>>,
+    /// Examples which are turned into tests
+    #[darling(multiple, rename = "example")]
+    pub examples: Vec<ExampleAttributes>,
+    /// File with more examples, relative to the crate root
+    #[darling(rename = "examples")]
+    pub examples_file: Option<syn::LitStr>,
+    /// Function creating the solution, instead of `Default::default`
+    pub constructor: Option<syn::Path>,
 }
 
 impl AocAttributes {
     fn autocorrect(self) -> darling::Result<Self> {
-        Ok(Self {
-            day: validate_day(self.day)?,
+        let mut errors = darling::Error::accumulator();
+        for example in self.examples.iter() {
+            if example.input.is_none() {
+                errors.push(darling::Error::missing_field("input"));
+            }
+            errors.handle(example.validate());
+        }
+        if let Some(day) = &self.day {
+            errors.handle(validate_day(**day, day.span()));
+        }
+        errors.finish_with(self)
+    }
+}
+
+/// Example input with the expected answers
+///
+/// `#[aoc_puzzle(day = 1, example(input = "...", part1 = 11, config(size = 6), param(space = "11,7")))]`
+#[derive(Debug, FromMeta)]
+pub struct ExampleAttributes {
+    pub name: Option<syn::Ident>,
+    pub input: Option<String>,
+    pub part1: Option<ExpectedAnswer>,
+    pub part2: Option<ExpectedAnswer>,
+    /// Solution fields to override, the other fields use `Default`
+    #[darling(default)]
+    pub config: HashMap<syn::Ident, ConfigValue>,
+    /// Parameters to set from text, like `--param` on the command line
+    #[darling(default)]
+    pub param: HashMap<String, String>,
+}
+
+impl ExampleAttributes {
+    pub fn validate(&self) -> darling::Result<()> {
+        if self.part1.is_none() && self.part2.is_none() {
+            return Err(darling::Error::custom(
+                "example needs an expected answer, set `part1` and/or `part2`",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Expected answer, numbers are compared as text so they do not need a type suffix
+#[derive(Debug)]
+pub struct ExpectedAnswer(TokenStream);
+
+impl FromMeta for ExpectedAnswer {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        Ok(match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => {
+                let digits = int.base10_digits();
+                Self(quote!(#digits))
+            }
+            expr => Self(expr.to_token_stream()),
         })
     }
 }
 
-pub fn validate_day(day: Option<u32>) -> Result<Option<u32>, darling::Error> {
-    if day.is_some_and(|d| !(1..=25).contains(&d)) {
-        Err(darling::Error::custom("day must be between 1 and 25"))
-    } else {
+impl ToTokens for ExpectedAnswer {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens);
+    }
+}
+
+/// Field value, used as written
+#[derive(Debug)]
+pub struct ConfigValue(syn::Expr);
+
+impl FromMeta for ConfigValue {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        Ok(Self(expr.clone()))
+    }
+}
+
+impl ToTokens for ConfigValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.0.to_tokens(tokens);
+    }
+}
+
+pub fn validate_day(day: u32, span: proc_macro2::Span) -> Result<u32, darling::Error> {
+    if (1..=25).contains(&day) {
         Ok(day)
+    } else {
+        Err(syn::Error::new(span, "day must be between 1 and 25").into())
     }
 }
@@ -0,0 +1,185 @@
+use darling::{ast::NestedMeta, FromMeta};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use super::attributes::{AocAttributes, ExampleAttributes};
+
+const HEADER: &str = "---";
+
+/// Example with its input resolved
+struct Example {
+    name: syn::Ident,
+    input: String,
+    attributes: ExampleAttributes,
+}
+
+/// Generate a test per example and part
+///
+/// `solution` creates the solution, fields set with `config` are added to it.
+pub(crate) fn example_tests(
+    ident: &syn::Ident,
+    solution: &TokenStream,
+    args: AocAttributes,
+) -> Result<TokenStream, darling::Error> {
+    let mut examples = vec![];
+    for attributes in args.examples {
+        let input = attributes.input.clone().unwrap_or_default();
+        examples.push((attributes, input));
+    }
+    let mut tracked_file = None;
+    if let Some(path) = args.examples_file {
+        examples.extend(read_examples_file(&path)?);
+        let path = path.value();
+        tracked_file = Some(quote! {
+            const _: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path));
+        });
+    }
+    if examples.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    let tests = examples
+        .into_iter()
+        .enumerate()
+        .map(|(i, (attributes, input))| Example {
+            name: attributes
+                .name
+                .clone()
+                .unwrap_or_else(|| format_ident!("example_{}", i + 1)),
+            input,
+            attributes,
+        })
+        .map(|example| example_test(ident, solution, example));
+
+    Ok(quote! {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            #tracked_file
+            #(#tests)*
+        }
+    })
+}
+
+fn example_test(ident: &syn::Ident, solution: &TokenStream, example: Example) -> TokenStream {
+    let Example {
+        name,
+        input,
+        attributes,
+    } = example;
+    let solution = if attributes.config.is_empty() {
+        solution.clone()
+    } else {
+        let (fields, values): (Vec<_>, Vec<_>) = attributes.config.iter().unzip();
+        quote! {
+            #ident {
+                #(#fields: #values,)*
+                ..#solution
+            }
+        }
+    };
+    let (params, values): (Vec<_>, Vec<_>) = attributes.param.iter().unzip();
+    let parts = [
+        ("part1", attributes.part1.as_ref()),
+        ("part2", attributes.part2.as_ref()),
+    ];
+    let tests = parts
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (part, expected))| Some((i, format_ident!("{}", part), expected?)))
+        .map(|(i, part, expected)| {
+            let test_name = format_ident!("{}_{}", name, part);
+            let index = syn::Index::from(i);
+            quote! {
+                #[test]
+                #[allow(clippy::needless_update)]
+                fn #test_name() {
+                    #[allow(unused_mut)]
+                    let mut solution = #solution;
+                    #(aoc_core::PuzzleParams::set_param(&mut solution, #params, #values).unwrap();)*
+                    let puzzle = aoc_core::Puzzle::from(#input);
+                    let parsed = aoc_core::ParsedPuzzleSolution::parse(&solution, &puzzle).unwrap();
+                    let result = aoc_core::ParsedPuzzleSolution::#part(&solution, &parsed);
+                    assert_eq!(result, aoc_core::Answer::from(#expected));
+                    if let Some(both) = aoc_core::ParsedPuzzleSolution::solve_both(&solution, &parsed) {
+                        assert_eq!(both.#index, aoc_core::Answer::from(#expected), "solve_both");
+                    }
+                }
+            }
+        });
+    quote!(#(#tests)*)
+}
+
+/// Read examples from a file, every example starts with a header line with
+/// the same settings as the `example` attribute, followed by the input:
+///
+/// ```text
+/// --- part1 = 2
+/// 0123
+/// --- name = "larger", part1 = 36, part2 = 81
+/// 89010123
+/// 78121874
+/// ```
+///
+/// Errors point at the path in the attribute.
+fn read_examples_file(
+    lit: &syn::LitStr,
+) -> Result<Vec<(ExampleAttributes, String)>, darling::Error> {
+    let path = lit.value();
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let file = std::path::Path::new(&root).join(&path);
+    let content = std::fs::read_to_string(&file).map_err(|e| {
+        syn::Error::new(
+            lit.span(),
+            format!("Failed to read examples from {}: {}", file.display(), e),
+        )
+    })?;
+
+    let mut examples: Vec<(ExampleAttributes, Vec<&str>)> = vec![];
+    for (number, line) in content.lines().enumerate() {
+        if let Some(header) = line.strip_prefix(HEADER) {
+            let attributes = parse_header(header).map_err(|e| {
+                syn::Error::new(lit.span(), format!("{} line {}: {}", path, number + 1, e))
+            })?;
+            examples.push((attributes, vec![]));
+        } else if let Some((_, input)) = examples.last_mut() {
+            input.push(line);
+        } else if !line.trim().is_empty() {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "{} line {}: expected a `{}` header before the input",
+                    path,
+                    number + 1,
+                    HEADER
+                ),
+            )
+            .into());
+        }
+    }
+    Ok(examples
+        .into_iter()
+        .map(|(attributes, input)| {
+            (
+                attributes,
+                input.join("\n").trim_end_matches('\n').to_string(),
+            )
+        })
+        .collect())
+}
+
+fn parse_header(header: &str) -> Result<ExampleAttributes, darling::Error> {
+    let tokens = header
+        .parse::<TokenStream>()
+        .map_err(|e| darling::Error::custom(e.to_string()))?;
+    let items = NestedMeta::parse_meta_list(tokens)?;
+    let attributes = ExampleAttributes::from_list(&items)?;
+    if attributes.input.is_some() {
+        return Err(darling::Error::custom(
+            "the input of a file example follows the header",
+        ));
+    }
+    attributes.validate()?;
+    Ok(attributes)
+}
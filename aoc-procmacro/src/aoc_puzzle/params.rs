@@ -0,0 +1,127 @@
+use darling::FromField;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use super::attributes::ConfigValue;
+
+/// Field with a `#[param(default = ...)]` attribute
+#[derive(Debug, FromField)]
+#[darling(attributes(param))]
+struct ParamField {
+    ident: Option<syn::Ident>,
+    ty: syn::Type,
+    default: Option<ConfigValue>,
+}
+
+/// Remove the `#[param]` attributes from the struct and implement `PuzzleParams`,
+/// structs with parameters also get a `Default` impl using the parameter defaults.
+/// Enums have no `fields` and no parameters.
+pub(crate) fn params_impl(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    mut input_fields: Option<&mut syn::Fields>,
+) -> Result<TokenStream, darling::Error> {
+    let mut errors = darling::Error::accumulator();
+    let mut params = vec![];
+    for field in input_fields.iter_mut().flat_map(|fields| fields.iter_mut()) {
+        if !field.attrs.iter().any(is_param) {
+            continue;
+        }
+        if field.ident.is_none() {
+            errors
+                .push(darling::Error::custom("parameters need a named field").with_span(&field.ty));
+        } else if let Some(param) = errors.handle(ParamField::from_field(field)) {
+            params.push(param);
+        }
+        field.attrs.retain(|a| !is_param(a));
+    }
+    errors.finish()?;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let names = params
+        .iter()
+        .map(|p| p.ident.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>();
+    let fields = params.iter().map(|p| &p.ident).collect::<Vec<_>>();
+    let type_names = params.iter().map(|p| type_name(&p.ty));
+
+    let default_impl = if params.is_empty() {
+        quote!()
+    } else {
+        let values = input_fields
+            .iter()
+            .flat_map(|fields| fields.iter())
+            .map(|field| {
+                let value = params
+                    .iter()
+                    .find(|p| p.ident == field.ident)
+                    .and_then(|p| p.default.as_ref())
+                    .map_or_else(
+                        || quote!(::core::default::Default::default()),
+                        |default| default.to_token_stream(),
+                    );
+                let field = &field.ident;
+                quote!(#field: #value)
+            });
+        quote! {
+            impl #impl_generics ::core::default::Default for #ident #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self { #(#values),* }
+                }
+            }
+        }
+    };
+
+    let params_list = if params.is_empty() {
+        quote!(Vec::new())
+    } else {
+        quote! {
+            let default = <Self as ::core::default::Default>::default();
+            vec![#(aoc_core::Param {
+                name: #names,
+                type_name: #type_names,
+                default: aoc_core::ParamValue::to_param(&default.#fields),
+                value: aoc_core::ParamValue::to_param(&self.#fields),
+            }),*]
+        }
+    };
+
+    Ok(quote! {
+        #default_impl
+
+        impl #impl_generics aoc_core::PuzzleParams for #ident #ty_generics #where_clause {
+            fn params(&self) -> Vec<aoc_core::Param> {
+                #params_list
+            }
+
+            #[allow(unused_variables)]
+            fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    #(#names => {
+                        self.#fields = aoc_core::ParamValue::from_param(value)
+                            .map_err(|e| format!("Invalid value for {}: {}", name, e))?;
+                        Ok(())
+                    })*
+                    _ => Err(format!("Unknown parameter '{}'", name)),
+                }
+            }
+        }
+    })
+}
+
+fn is_param(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("param")
+}
+
+// Type as written, without the spacing of the token stream
+fn type_name(ty: &syn::Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" ,", ",")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" :: ", "::")
+}
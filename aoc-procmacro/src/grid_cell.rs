@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, spanned::Spanned, LitChar, Token};
+
+pub(crate) fn grid_cell_impl(input: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = match syn::parse2(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+    match expand(&input) {
+        Ok(expanded) => expanded,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let data = match &input.data {
+        syn::Data::Enum(data) => data,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "GridCell can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut from_arms = Vec::new();
+    let mut to_arms = Vec::new();
+    // Variant of every character, a character can only parse to one variant
+    let mut seen = HashMap::new();
+    for variant in data.variants.iter() {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.fields.span(),
+                "GridCell variants cannot have fields",
+            ));
+        }
+        let chars = cell_chars(variant)?;
+        let ident = &variant.ident;
+        for c in chars.iter() {
+            if let Some(previous) = seen.insert(c.value(), ident) {
+                return Err(syn::Error::new(
+                    c.span(),
+                    format!("{:?} is already the cell of `{}`", c.value(), previous),
+                ));
+            }
+        }
+        let first = &chars[0];
+        from_arms.push(quote! { #(#chars)|* => Some(Self::#ident), });
+        to_arms.push(quote! { Self::#ident => #first, });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics aoc_core::tools::GridCell for #ident #ty_generics #where_clause {
+            fn from_char(c: char) -> Option<Self> {
+                match c {
+                    #(#from_arms)*
+                    _ => None,
+                }
+            }
+            fn to_char(&self) -> char {
+                match self {
+                    #(#to_arms)*
+                }
+            }
+        }
+    })
+}
+
+// Characters of the `#[cell('#', 'O')]` attribute, the first one is used when printing
+fn cell_chars(variant: &syn::Variant) -> syn::Result<Vec<LitChar>> {
+    let attr = variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("cell"))
+        .ok_or_else(|| {
+            syn::Error::new(
+                variant.ident.span(),
+                "missing `#[cell('x')]` attribute on GridCell variant",
+            )
+        })?;
+    let chars = attr.parse_args_with(Punctuated::<LitChar, Token![,]>::parse_terminated)?;
+    if chars.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "`#[cell(..)]` needs at least one character",
+        ));
+    }
+    Ok(chars.into_iter().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_reject_duplicate_chars() {
+        let input = quote! {
+            enum Tile {
+                #[cell('#')]
+                Wall,
+                #[cell('.', '#')]
+                Open,
+            }
+        };
+        let error = expand(&syn::parse2(input).unwrap()).unwrap_err();
+        assert_eq!(error.to_string(), "'#' is already the cell of `Wall`");
+    }
+}
@@ -2,6 +2,7 @@ use proc_macro::TokenStream;
 
 mod aoc;
 mod aoc_puzzle;
+mod grid_cell;
 
 /// Derive macro for the AocPuzzles
 ///
@@ -23,7 +24,41 @@ mod aoc_puzzle;
 /// }
 /// ```
 ///
-/// Before you can call this macro in your code, you need to set `#[aoc(year = 2024)] on your main func`.
+/// Examples are turned into tests, an example can check one or both parts and
+/// override fields of the solution, the other fields use `Default`:
+/// ```no_compile
+/// #[aoc_puzzle(
+///     day = 18,
+///     example(input = "5,4\n4,2", part1 = 22, part2 = "6,1", config(size = (6, 6))),
+///     examples = "src/solutions/examples/day18.txt",
+/// )]
+/// ```
+/// The `examples` file is relative to the crate root, every example starts with
+/// a `--- part1 = 22, name = "small"` header line followed by the input.
+///
+/// Fields marked with `#[param(default = ...)]` are parameters, they can be set
+/// with `--param name=value` on the command line or `param(name = "value")` in
+/// an example. The struct gets a `Default` impl using the parameter defaults:
+/// ```no_compile
+/// #[aoc_puzzle(day = 14, example(input = "...", part1 = 12, param(space = "11,7")))]
+/// pub struct Day {
+///     #[param(default = (101, 103))]
+///     space: (i32, i32),
+/// }
+/// ```
+///
+/// The macro works on structs, tuple structs, enums and generic types (the
+/// generics need defaults). The solution is created with `Default`, or with a
+/// `constructor`; `register_solution_with` registers a configured instance:
+/// ```no_compile
+/// #[aoc_puzzle(day = 6, constructor = Mode::new)]
+/// pub enum Mode { Fast, Slow }
+///
+/// register_solution_with(&mut collection, Mode::Slow);
+/// ```
+///
+/// Before you can call this macro in your code, you need to set `#[aoc(year = 2024)]` on your main func.
+/// Every day can only have one solution.
 #[proc_macro_attribute]
 pub fn aoc_puzzle(args: TokenStream, input: TokenStream) -> TokenStream {
     aoc_puzzle::aoc_puzzle_impl(args.into(), input.into()).into()
@@ -39,7 +74,32 @@ pub fn aoc_puzzle(args: TokenStream, input: TokenStream) -> TokenStream {
 /// fn main() {
 /// }
 /// ```
+///
+/// When the puzzle text of a day can not be fetched it is left out of the docs,
+/// set `fetch_errors = "warn"` or `fetch_errors = "error"` to get a compiler
+/// warning or error instead.
 #[proc_macro_attribute]
 pub fn aoc(args: TokenStream, input: TokenStream) -> TokenStream {
     aoc::aoc_impl(args.into(), input.into()).into()
 }
+
+/// Derive macro for grid cells
+///
+/// Implements `aoc_core::tools::GridCell` for an enum of unit variants, every
+/// variant lists the characters it is parsed from. The first character is used
+/// when converting back, a character can only belong to one variant.
+///
+/// Usage:
+/// ```no_compile
+/// #[derive(GridCell)]
+/// enum Tile {
+///     #[cell('#')]
+///     Wall,
+///     #[cell('.', 'S', 'E')]
+///     Open,
+/// }
+/// ```
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn grid_cell(input: TokenStream) -> TokenStream {
+    grid_cell::grid_cell_impl(input.into()).into()
+}
@@ -1,34 +1,86 @@
-use std::time::Duration;
+use std::time::SystemTime;
 
 use aoc_core::SolutionCollection;
 use aoc_solutions_2024::solutions::get_collection;
-use criterion::{criterion_group, criterion_main, Criterion};
+use clap::Parser;
+use criterion::{Criterion, Throughput};
 
-fn benchmarks(c: &mut Criterion) {
+mod harness;
+
+use harness::{BenchOptions, Summary};
+
+fn main() {
+    let options = BenchOptions::parse();
     let collection = get_collection();
-    for day in collection.get_days() {
-        bench_day(c, day, &collection);
+    let mut days = collection
+        .get_days()
+        .into_iter()
+        .filter(|day| options.selects(*day))
+        .collect::<Vec<_>>();
+    days.sort();
+    if !options.bench {
+        // `cargo test --benches`, check that every phase runs
+        for day in days {
+            let prepared = collection.prepare_bench(&day);
+            prepared.parse();
+            prepared.solve();
+            println!("day{}: ok", day);
+        }
+        return;
+    }
+
+    let started = SystemTime::now();
+    let mut c = options.criterion();
+    for day in &days {
+        bench_day(&mut c, *day, &collection, &options);
+    }
+    c.final_summary();
+
+    let summary = Summary::read(&harness::output_directory(), &days, started);
+    summary.print();
+    if let Some(limit) = options.fail_on_regression {
+        let regressions = summary.regressions(limit);
+        for regression in &regressions {
+            eprintln!(
+                "Regression: {} is {:.1}% slower (limit {}%)",
+                regression.name,
+                regression.change * 100.0,
+                limit
+            );
+        }
+        if !regressions.is_empty() {
+            std::process::exit(1);
+        }
     }
 }
 
-fn bench_day(c: &mut Criterion, day: u32, collection: &SolutionCollection) {
+fn bench_day(c: &mut Criterion, day: u32, collection: &SolutionCollection, options: &BenchOptions) {
     let mut group = c.benchmark_group(format!("day{}", day).as_str());
-    // group.sample_size(20);
-    group.measurement_time(Duration::from_secs(20));
-    group.sampling_mode(criterion::SamplingMode::Flat);
-    let (part1, part2) = collection.prepare_bench(&day);
+    if let Some(samples) = options.samples_of(day) {
+        group.sample_size(samples);
+    }
+    group.sampling_mode(options.sampling_mode());
+    let prepared = collection.prepare_bench(&day);
+    group.throughput(Throughput::Bytes(prepared.input_size() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            prepared.parse();
+        })
+    });
     group.bench_function("part1", |b| {
         b.iter(|| {
-            part1();
+            prepared.part1();
         })
     });
     group.bench_function("part2", |b| {
         b.iter(|| {
-            part2();
+            prepared.part2();
+        })
+    });
+    group.bench_function("solve", |b| {
+        b.iter(|| {
+            prepared.solve();
         })
     });
     group.finish();
 }
-
-criterion_group!(benches, benchmarks);
-criterion_main!(benches);
@@ -0,0 +1,144 @@
+//! Command line options and the summary of the puzzle benchmarks
+
+use std::{path::PathBuf, time::Duration};
+
+use clap::{Parser, ValueEnum};
+use criterion::{Criterion, SamplingMode};
+
+mod summary;
+
+pub use summary::Summary;
+
+/// Benchmark the puzzle solutions
+///
+/// `cargo bench --bench benchmarks -- [OPTIONS] [FILTER]`
+#[derive(Parser, Debug)]
+pub struct BenchOptions {
+    /// Only run benchmarks whose name matches the filter, like `day1/` or `parse`
+    pub filter: Option<String>,
+    /// Set by `cargo bench`, without it every benchmark runs once as a test
+    #[arg(long, hide = true)]
+    pub bench: bool,
+    /// How long to measure every benchmark
+    #[arg(long, value_enum, default_value_t = Profile::Quick)]
+    pub profile: Profile,
+    /// Number of samples for a day, like `--samples 6=10` for a slow day 6
+    #[arg(long, value_name = "DAY=N", value_parser = parse_samples)]
+    pub samples: Vec<(u32, usize)>,
+    /// Save the results under a named baseline
+    #[arg(long, value_name = "NAME", conflicts_with = "baseline")]
+    pub save_baseline: Option<String>,
+    /// Compare with a named baseline, without overwriting it
+    #[arg(long, value_name = "NAME")]
+    pub baseline: Option<String>,
+    /// Exit with an error when a benchmark got slower than this, like `10%`
+    #[arg(long, value_name = "PERCENT", value_parser = parse_percentage)]
+    pub fail_on_regression: Option<f64>,
+    /// Disable the plots and HTML reports
+    #[arg(long)]
+    pub noplot: bool,
+}
+
+/// Measurement settings of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Profile {
+    /// Few short samples, to spot changes while working on a day
+    Quick,
+    /// Long flat sampling, for stable numbers
+    Full,
+}
+
+impl BenchOptions {
+    pub fn criterion(&self) -> Criterion {
+        let criterion = Criterion::default().output_directory(&output_directory());
+        let mut criterion = match self.profile {
+            Profile::Quick => criterion
+                .sample_size(10)
+                .warm_up_time(Duration::from_millis(500))
+                .measurement_time(Duration::from_secs(2)),
+            Profile::Full => criterion
+                .sample_size(100)
+                .warm_up_time(Duration::from_secs(3))
+                .measurement_time(Duration::from_secs(20)),
+        };
+        if let Some(filter) = &self.filter {
+            criterion = criterion.with_filter(filter);
+        }
+        if self.noplot {
+            criterion = criterion.without_plots();
+        }
+        match (&self.save_baseline, &self.baseline) {
+            (Some(name), _) => criterion.save_baseline(name.clone()),
+            (None, Some(name)) => criterion.retain_baseline(name.clone(), false),
+            (None, None) => criterion,
+        }
+    }
+
+    pub fn sampling_mode(&self) -> SamplingMode {
+        match self.profile {
+            Profile::Quick => SamplingMode::Auto,
+            Profile::Full => SamplingMode::Flat,
+        }
+    }
+
+    /// Whether the filter can match a benchmark of the day, so other days are not
+    /// loaded; filters using regex syntax are left to criterion
+    pub fn selects(&self, day: u32) -> bool {
+        match &self.filter {
+            Some(filter) if filter.chars().all(|c| c.is_alphanumeric() || c == '/') => {
+                Summary::PHASES
+                    .iter()
+                    .any(|phase| format!("day{}/{}", day, phase).contains(filter.as_str()))
+            }
+            _ => true,
+        }
+    }
+
+    /// Sample size override of the day
+    pub fn samples_of(&self, day: u32) -> Option<usize> {
+        self.samples
+            .iter()
+            .rev()
+            .find(|(d, _)| *d == day)
+            .map(|(_, samples)| *samples)
+    }
+}
+
+/// Where criterion stores its results, the same place `cargo bench` uses
+pub fn output_directory() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../target")))
+        .join("criterion")
+}
+
+fn parse_samples(samples: &str) -> Result<(u32, usize), String> {
+    let (day, count) = samples
+        .split_once('=')
+        .ok_or_else(|| format!("Expected DAY=N, got '{}'", samples))?;
+    let day = day
+        .trim()
+        .trim_start_matches("day")
+        .parse()
+        .map_err(|e| format!("Invalid day '{}': {}", day, e))?;
+    let count = count
+        .trim()
+        .parse()
+        .map_err(|e| format!("Invalid sample size '{}': {}", count, e))?;
+    if count < 10 {
+        return Err("Criterion needs at least 10 samples".to_string());
+    }
+    Ok((day, count))
+}
+
+fn parse_percentage(percentage: &str) -> Result<f64, String> {
+    let value = percentage.trim().trim_end_matches('%');
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|p| *p >= 0.0)
+        .ok_or_else(|| format!("Expected a percentage like 10%, got '{}'", percentage))
+}
@@ -0,0 +1,104 @@
+use std::{
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+/// Results of the benchmarks measured in this run, read from the criterion output
+pub struct Summary {
+    rows: Vec<Row>,
+}
+
+struct Row {
+    day: u32,
+    /// Mean time and relative change to the baseline of every phase
+    phases: Vec<Option<(Duration, Option<f64>)>>,
+}
+
+/// Found regression, a benchmark and its relative change
+pub struct Regression {
+    pub name: String,
+    pub change: f64,
+}
+
+impl Summary {
+    pub const PHASES: [&'static str; 4] = ["parse", "part1", "part2", "solve"];
+
+    /// Read the estimates written since `started`, older results are from other runs
+    pub fn read(output: &Path, days: &[u32], started: SystemTime) -> Self {
+        let rows = days
+            .iter()
+            .map(|&day| Row {
+                day,
+                phases: Self::PHASES
+                    .iter()
+                    .map(|phase| {
+                        let dir = output.join(format!("day{}", day)).join(phase);
+                        let mean = read_mean(&dir.join("new/estimates.json"), started)?;
+                        let change = read_mean(&dir.join("change/estimates.json"), started);
+                        // Criterion writes nanoseconds, a negative or huge estimate leaves the phase out
+                        let mean = Duration::try_from_secs_f64(mean / 1e9).ok()?;
+                        Some((mean, change))
+                    })
+                    .collect(),
+            })
+            .filter(|row| row.phases.iter().any(|phase| phase.is_some()))
+            .collect();
+        Self { rows }
+    }
+
+    pub fn print(&self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        println!();
+        print!("{:<6}", "");
+        for phase in Self::PHASES {
+            print!("{:>22}", phase);
+        }
+        println!();
+        for row in &self.rows {
+            print!("{:<6}", format!("day{}", row.day));
+            for phase in &row.phases {
+                let cell = match phase {
+                    Some((mean, Some(change))) => {
+                        format!("{:.2?} ({:+.1}%)", mean, change * 100.0)
+                    }
+                    Some((mean, None)) => format!("{:.2?}", mean),
+                    None => "-".to_string(),
+                };
+                print!("{:>22}", cell);
+            }
+            println!();
+        }
+    }
+
+    /// Benchmarks which got slower than `limit` percent
+    pub fn regressions(&self, limit: f64) -> Vec<Regression> {
+        self.rows
+            .iter()
+            .flat_map(|row| {
+                Self::PHASES
+                    .iter()
+                    .zip(row.phases.iter())
+                    .filter_map(move |(phase, result)| {
+                        let change = (*result)?.1?;
+                        (change * 100.0 > limit).then(|| Regression {
+                            name: format!("day{}/{}", row.day, phase),
+                            change,
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Point estimate of the mean in a criterion `estimates.json`
+fn read_mean(path: &Path, started: SystemTime) -> Option<f64> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.modified().ok()? < started {
+        return None;
+    }
+    let estimates = std::fs::read_to_string(path).ok()?;
+    let estimates = serde_json::from_str::<serde_json::Value>(&estimates).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
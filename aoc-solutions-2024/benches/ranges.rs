@@ -0,0 +1,94 @@
+use aoc_core::tools::RangeSet;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const DIGITS: usize = 20_000;
+
+// Disk map in the format of day 9, alternating file and free sizes
+fn create_disk_map() -> Vec<usize> {
+    (0..DIGITS)
+        .map(|i| {
+            let size = (i * 7 + i / 3) % 10;
+            if i % 2 == 0 {
+                size.max(1)
+            } else {
+                size
+            }
+        })
+        .collect()
+}
+
+// Move whole files to the first free space by scanning every block
+fn compact_blocks(map: &[usize]) -> usize {
+    let mut blocks = vec![];
+    let mut files = vec![];
+    for (i, size) in map.iter().enumerate() {
+        if i % 2 == 0 {
+            files.push((blocks.len(), *size));
+            blocks.extend(std::iter::repeat_n(Some(i / 2), *size));
+        } else {
+            blocks.extend(std::iter::repeat_n(None, *size));
+        }
+    }
+    for (id, (start, size)) in files.iter().enumerate().rev() {
+        let mut run = 0;
+        for index in 0..*start {
+            run = if blocks[index].is_none() { run + 1 } else { 0 };
+            if run == *size {
+                let target = index + 1 - size;
+                blocks[target..=index].fill(Some(id));
+                blocks[*start..start + size].fill(None);
+                break;
+            }
+        }
+    }
+    checksum(
+        blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| b.map(|id| (id, i..i + 1))),
+    )
+}
+
+// Move whole files to the first free span found in a range set
+fn compact_ranges(map: &[usize]) -> usize {
+    let mut free = RangeSet::new();
+    let mut files = vec![];
+    let mut position = 0;
+    for (i, size) in map.iter().enumerate() {
+        let span = position..position + size;
+        position += size;
+        if i % 2 == 0 {
+            files.push(span);
+        } else {
+            free.insert(span);
+        }
+    }
+    for file in files.iter_mut().rev() {
+        let size = file.len();
+        if let Some(span) = free.first_span(size).filter(|s| s.start < file.start) {
+            let target = span.start..span.start + size;
+            free.remove(target.clone());
+            free.insert(file.clone());
+            *file = target;
+        }
+    }
+    checksum(files.into_iter().enumerate())
+}
+
+fn checksum(files: impl Iterator<Item = (usize, std::ops::Range<usize>)>) -> usize {
+    files.map(|(id, span)| id * span.sum::<usize>()).sum()
+}
+
+fn benchmarks(c: &mut Criterion) {
+    let map = create_disk_map();
+    assert_eq!(compact_blocks(&map), compact_ranges(&map));
+
+    let mut group = c.benchmark_group("ranges");
+    group.sample_size(10);
+    group.bench_function("block_scan", |b| b.iter(|| compact_blocks(&map)));
+    group.bench_function("range_set", |b| b.iter(|| compact_ranges(&map)));
+    group.finish();
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use aoc_core::tools::{AStarBuilder, BfsBuilder, DijkstraBuilder, Heuristic};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SIZE: usize = 70;
+
+// Scattered bytes, similar to the corrupted memory of day 18
+fn create_obstacles() -> HashSet<(usize, usize)> {
+    (0..=SIZE)
+        .flat_map(|x| (0..=SIZE).map(move |y| (x, y)))
+        .filter(|(x, y)| (x * 7 + y * 13) % 5 == 0 && (x * y) % 3 == 1)
+        .filter(|c| *c != (SIZE, SIZE))
+        .collect()
+}
+
+fn benchmarks(c: &mut Criterion) {
+    let obstacles = create_obstacles();
+    let (start, end) = ((0, 0), (SIZE, SIZE));
+    let bounds = ((0, 0), (SIZE, SIZE));
+
+    let expanded = [
+        (
+            "bfs",
+            BfsBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .run()
+                .map_or(0, |r| r.expanded()),
+        ),
+        (
+            "dijkstra",
+            DijkstraBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .run()
+                .expanded(),
+        ),
+        (
+            "astar",
+            AStarBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .run()
+                .expanded(),
+        ),
+    ];
+    for (name, expanded) in expanded {
+        println!("{}: expanded {} nodes", name, expanded);
+    }
+
+    let mut group = c.benchmark_group("search");
+    group.bench_function("bfs", |b| {
+        b.iter(|| {
+            BfsBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .run()
+        })
+    });
+    group.bench_function("dijkstra", |b| {
+        b.iter(|| {
+            DijkstraBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .run()
+        })
+    });
+    group.bench_function("astar_zero", |b| {
+        b.iter(|| {
+            AStarBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .with_heuristic(Heuristic::Zero)
+                .run()
+        })
+    });
+    group.bench_function("astar_manhattan", |b| {
+        b.iter(|| {
+            AStarBuilder::new(start, end)
+                .with_obstacles(obstacles.clone())
+                .with_bounds(bounds.0, bounds.1)
+                .with_heuristic(Heuristic::Manhattan)
+                .run()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -2,3 +2,6 @@
 #[allow(dead_code)]
 fn lib() {}
 pub mod solutions;
+
+#[cfg(test)]
+mod puzzle_kinds;
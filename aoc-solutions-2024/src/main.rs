@@ -1,3 +1,6 @@
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
+
+use aoc_core::timer::{parse_duration, Budget};
 use clap::Parser;
 
 mod solutions;
@@ -14,13 +17,111 @@ struct Args {
     /// Puzzle day to run
     #[arg(short, long)]
     day: Option<u32>,
+    /// Set a solution parameter, like `--param space=11,7`; applies to the selected day,
+    /// or to every day with that parameter
+    #[arg(long, value_name = "NAME=VALUE", value_parser = parse_param)]
+    param: Vec<(String, String)>,
+    /// List the parameters of the solutions instead of running them
+    #[arg(long)]
+    list_params: bool,
+    /// Check that solutions solving both parts in one pass agree with the separate parts
+    #[arg(long)]
+    check: bool,
+    /// Print a timing tree of every phase, with allocations when built with `--features alloc-stats`
+    #[arg(long)]
+    profile: bool,
+    /// Time every phase this many times and report the median and statistics
+    #[arg(long, value_name = "N", default_value_t = NonZeroUsize::MIN)]
+    repeat: NonZeroUsize,
+    /// Untimed runs of every phase before the timed ones
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    warmup: u32,
+    /// Time budgets from a TOML file; exits with 1 when a day, part or the total is over budget
+    #[arg(long, value_name = "FILE")]
+    budget: Option<PathBuf>,
+    /// Budget of all days together, like `1s`
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    total_budget: Option<Duration>,
+    /// Budget of every day like `100ms`, or of a single day like `6=400ms`
+    #[arg(long, value_name = "[DAY=]DURATION", value_parser = parse_day_budget)]
+    day_budget: Vec<(Option<u32>, Duration)>,
+    /// Budget of every part, or of both parts when solved in one pass
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    part_budget: Option<Duration>,
+}
+
+fn parse_param(param: &str) -> Result<(String, String), String> {
+    param
+        .split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("Expected NAME=VALUE, got '{}'", param))
+}
+
+fn parse_day_budget(budget: &str) -> Result<(Option<u32>, Duration), String> {
+    let Some((day, duration)) = budget.split_once('=') else {
+        return Ok((None, parse_duration(budget)?));
+    };
+    let day = day
+        .trim()
+        .parse()
+        .map_err(|e| format!("Invalid day '{}': {}", day, e))?;
+    Ok((Some(day), parse_duration(duration)?))
+}
+
+/// The budget file with the budgets of the command line on top
+fn budget(args: &Args) -> Result<Budget, String> {
+    let mut budget = match &args.budget {
+        Some(path) => Budget::load(path)?,
+        None => Budget::default(),
+    };
+    budget.total = args.total_budget.or(budget.total);
+    budget.part = args.part_budget.or(budget.part);
+    for (day, duration) in &args.day_budget {
+        match day {
+            Some(day) => {
+                budget.days.insert(*day, *duration);
+            }
+            None => budget.day = Some(*duration),
+        }
+    }
+    Ok(budget)
 }
 
 #[aoc_core::aoc(year = 2024)]
 fn main() {
     let args = Args::parse();
+    let budget = budget(&args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    });
     if let Some(session_id) = args.aoc_session {
         aoc_core::set_session(session_id);
     }
-    solutions::run(args.day);
+    let mut puzzles = solutions::get_collection();
+    for (name, value) in args.param {
+        if let Err(e) = puzzles.set_param(args.day, &name, &value) {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
+    if args.list_params {
+        puzzles.list_params(args.day);
+        return;
+    }
+    if args.check {
+        if !puzzles.check(args.day) {
+            std::process::exit(1);
+        }
+        return;
+    }
+    puzzles.set_profiling(args.profile);
+    puzzles.set_repetitions(args.repeat, args.warmup as usize);
+    let times = puzzles.run(args.day);
+    if !budget.is_empty() {
+        let report = budget.report(&times);
+        print!("{}", report);
+        if !report.passed() {
+            std::process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,138 @@
+//! Solutions in the shapes `aoc_puzzle` accepts besides a plain struct, their
+//! examples make sure the generated code compiles and runs
+
+mod with_constructor {
+    use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+
+    #[aoc_puzzle(
+        day = 1,
+        constructor = Fold::product,
+        example(input = "2\n3\n4", part1 = 24, part2 = 3)
+    )]
+    pub enum Fold {
+        Sum,
+        Product,
+    }
+
+    impl Fold {
+        fn product() -> Self {
+            Fold::Product
+        }
+
+        fn numbers(puzzle: &Puzzle) -> Vec<u64> {
+            puzzle
+                .get_input()
+                .lines()
+                .filter_map(|line| line.parse().ok())
+                .collect()
+        }
+    }
+
+    impl PuzzleSolution for Fold {
+        fn part1(&self, puzzle: &Puzzle) -> Answer {
+            match self {
+                Fold::Sum => Self::numbers(puzzle).iter().sum::<u64>().into(),
+                Fold::Product => Self::numbers(puzzle).iter().product::<u64>().into(),
+            }
+        }
+
+        fn part2(&self, puzzle: &Puzzle) -> Answer {
+            Self::numbers(puzzle).len().into()
+        }
+    }
+}
+
+mod tuple_struct {
+    use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+
+    #[aoc_puzzle(day = 2, example(input = "1,2,3", part1 = 3, part2 = 6))]
+    pub struct Separator(char);
+
+    impl Default for Separator {
+        fn default() -> Self {
+            Self(',')
+        }
+    }
+
+    impl PuzzleSolution for Separator {
+        fn part1(&self, puzzle: &Puzzle) -> Answer {
+            puzzle.get_input().split(self.0).count().into()
+        }
+
+        fn part2(&self, puzzle: &Puzzle) -> Answer {
+            puzzle
+                .get_input()
+                .split(self.0)
+                .map(|value| value.trim().parse::<u64>().unwrap_or(0))
+                .sum::<u64>()
+                .into()
+        }
+    }
+}
+
+mod generic {
+    use std::{fmt::Display, iter::Sum, str::FromStr};
+
+    use aoc_core::{aoc_puzzle, Answer, ParamValue, Puzzle, PuzzleSolution};
+
+    #[aoc_puzzle(
+        day = 3,
+        example(input = "1\n-2\n3", part1 = 4, part2 = 2),
+        example(input = "1\n-2\n3", part1 = 2, part2 = 3, param(min = "-5"))
+    )]
+    pub struct AtLeast<T = i64>
+    where
+        T: ParamValue + FromStr + Sum + PartialOrd + Display + Copy + Default + Send + Sync,
+    {
+        #[param(default = T::default())]
+        pub min: T,
+    }
+
+    impl<T> AtLeast<T>
+    where
+        T: ParamValue + FromStr + Sum + PartialOrd + Display + Copy + Default + Send + Sync,
+    {
+        fn numbers(&self, puzzle: &Puzzle) -> Vec<T> {
+            puzzle
+                .get_input()
+                .lines()
+                .filter_map(|line| line.parse().ok())
+                .filter(|n| *n >= self.min)
+                .collect()
+        }
+    }
+
+    impl<T> PuzzleSolution for AtLeast<T>
+    where
+        T: ParamValue + FromStr + Sum + PartialOrd + Display + Copy + Default + Send + Sync,
+    {
+        fn part1(&self, puzzle: &Puzzle) -> Answer {
+            self.numbers(puzzle)
+                .into_iter()
+                .sum::<T>()
+                .to_string()
+                .into()
+        }
+
+        fn part2(&self, puzzle: &Puzzle) -> Answer {
+            self.numbers(puzzle).len().into()
+        }
+    }
+}
+
+const _: () =
+    aoc_core::assert_unique_days(&[with_constructor::DAY, tuple_struct::DAY, generic::DAY]);
+
+#[test]
+fn registers_every_kind() {
+    let mut collection = aoc_core::SolutionCollection::default();
+    with_constructor::register_solution_with(&mut collection, with_constructor::Fold::Sum);
+    tuple_struct::register_solution(&mut collection);
+    generic::register_solution_with(&mut collection, generic::AtLeast::<i64> { min: -5 });
+    let mut days = collection.get_days();
+    days.sort();
+    assert_eq!(days, vec![1, 2, 3]);
+    let params = collection.get_params(&3);
+    assert_eq!(params[0].value, "-5");
+    assert_eq!(params[0].default, "0");
+}
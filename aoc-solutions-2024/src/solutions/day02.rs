@@ -72,13 +72,13 @@ mod tests {
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, 2.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&get_puzzle());
         assert_eq!(result, 4.into());
     }
 }
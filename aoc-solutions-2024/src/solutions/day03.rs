@@ -1,55 +1,64 @@
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
-use regex::Regex;
+use aoc_core::{
+    aoc_puzzle,
+    tools::parse::{literal, uint, Parser},
+    Answer, Puzzle, PuzzleSolution,
+};
 
-#[aoc_puzzle(day = 3)]
+#[aoc_puzzle(
+    day = 3,
+    example(
+        input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+        part1 = 161
+    ),
+    example(
+        input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        part2 = 48
+    )
+)]
 #[derive(Default)]
 pub struct Day;
 
-lazy_static::lazy_static! {
-    static ref PART1: Regex = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
-    static ref PART2: Regex = Regex::new(r"(?:(?:(mul)\((\d+),(\d+)\))|(do)\(\)|(don't)\(\))").unwrap();
-}
-
 impl PuzzleSolution for Day {
     fn part1(&self, puzzle: &Puzzle) -> Answer {
-        PART1.captures_iter(puzzle.input_as_str()).map(|cap| {
-            let a = cap[1].parse::<u32>().unwrap();
-            let b = cap[2].parse::<u32>().unwrap();
-            a * b
-        }).sum::<u32>().into()
+        mul()
+            .scan(puzzle.input_as_str())
+            .map(|(a, b)| a * b)
+            .sum::<u32>()
+            .into()
     }
 
     fn part2(&self, puzzle: &Puzzle) -> Answer {
+        let instruction = mul()
+            .map(|(a, b)| Instruction::Mul(a, b))
+            .or(literal("do()").map(|_| Instruction::Do))
+            .or(literal("don't()").map(|_| Instruction::Dont));
         let mut enabled = true;
-        PART2.captures_iter(puzzle.input_as_str()).map(|cap| {
-            let cmd = cap.get(1).or(cap.get(4)).or(cap.get(5)).expect("Command");
-            if cmd.as_str() == "mul" && enabled {
-                let a = cap[2].parse::<u32>().unwrap();
-                let b = cap[3].parse::<u32>().unwrap();
-                return a * b
-            } else if cmd.as_str() == "do" {
-                enabled = true;
-            } else if cmd.as_str() == "don't" {
-                enabled = false;
-            } 
-            0
-        }).sum::<u32>().into()
+        instruction
+            .scan(puzzle.input_as_str())
+            .map(|instruction| {
+                match instruction {
+                    Instruction::Mul(a, b) if enabled => return a * b,
+                    Instruction::Mul(..) => {}
+                    Instruction::Do => enabled = true,
+                    Instruction::Dont => enabled = false,
+                }
+                0
+            })
+            .sum::<u32>()
+            .into()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
-    #[test]
-    fn part1() {
-        let result = Day::default().part1(&Puzzle::from(r#"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"#));
-        assert_eq!(result, 161.into());
-    }
+enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
 
-    #[test]
-    fn part2() {
-        let result = Day::default().part2(&Puzzle::from(r#"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"#));
-        assert_eq!(result, 48.into());
-    }
-}
\ No newline at end of file
+fn mul() -> impl Parser<(u32, u32)> {
+    literal("mul(")
+        .ignore_then(uint())
+        .then_ignore(literal(","))
+        .then(uint())
+        .then_ignore(literal(")"))
+}
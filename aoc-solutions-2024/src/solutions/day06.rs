@@ -1,7 +1,11 @@
 use rayon::prelude::*;
 use std::collections::{BTreeSet, HashSet};
 
-use aoc_core::{aoc_puzzle, tools::Grid, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{
+    aoc_puzzle,
+    tools::{find_cycle_hashed, Grid},
+    Answer, Puzzle, PuzzleSolution,
+};
 
 #[aoc_puzzle(day = 6)]
 #[derive(Default)]
@@ -41,8 +45,7 @@ impl PuzzleSolution for Day {
                     return None;
                 }
                 let current_guard = *current_visited.last().unwrap();
-                let current_visited = current_visited.iter().cloned().collect::<BTreeSet<_>>();
-                if find_loop(&walls, &size, &cell.0, current_guard, current_visited) {
+                if find_loop(&walls, &size, &cell.0, current_guard) {
                     Some(cell.0)
                 } else {
                     None
@@ -82,24 +85,20 @@ fn find_loop(
     walls: &BTreeSet<(usize, usize)>,
     (width, height): &(usize, usize),
     extra_obstacle: &(usize, usize),
-    mut guard: GuardMove,
-    mut visited: BTreeSet<GuardMove>,
+    guard: GuardMove,
 ) -> bool {
-    while let Some(next_pos) = guard.1.move_pos(&guard.0) {
+    find_cycle_hashed(guard, |guard| {
+        let next_pos = guard.1.move_pos(&guard.0)?;
         if next_pos.0 >= *width || next_pos.1 >= *height {
-            break;
+            return None;
         }
         if &next_pos == extra_obstacle || walls.contains(&next_pos) {
-            visited.insert(guard);
-            guard.1 = guard.1.rotate();
+            Some((guard.0, guard.1.rotate()))
         } else {
-            guard.0 = next_pos;
+            Some((next_pos, guard.1))
         }
-        if visited.contains(&guard) {
-            return true;
-        }
-    }
-    false
+    })
+    .is_some()
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -150,13 +149,13 @@ mod tests {
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, 41.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&get_puzzle());
         assert_eq!(result, 6.into());
     }
 }
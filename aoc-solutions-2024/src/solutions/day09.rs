@@ -1,8 +1,12 @@
-use std::{collections::VecDeque, rc::Rc, str::FromStr};
+use std::{ops::Range, str::FromStr};
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, tools::RangeSet, Answer, Puzzle, PuzzleSolution};
 
-#[aoc_puzzle(day = 9)]
+#[aoc_puzzle(
+    day = 9,
+    example(input = "12345", part1 = 60),
+    example(input = "2333133121414131402", part1 = 1928, part2 = 2858)
+)]
 #[derive(Default)]
 pub struct Day;
 
@@ -24,82 +28,64 @@ impl PuzzleSolution for Day {
 
 #[derive(Debug)]
 struct Disk {
-    files: Vec<Rc<File>>,
-    blocks: Vec<Block>,
+    files: Vec<File>,
+    free: RangeSet<usize>,
 }
 
 impl Disk {
-    fn compress_blocks(self) -> Self {
-        let mut blocks = vec![];
-        let mut old_blocks = VecDeque::from(self.blocks.clone());
-        while !old_blocks.is_empty() {
-            match old_blocks.pop_front().unwrap() {
-                Block::File(f) => {
-                    blocks.push(Block::File(f));
-                    continue;
-                },
-                Block::Free => loop {
-                    match old_blocks.pop_back() {
-                        Some(Block::File(f)) => {
-                            blocks.push(Block::File(f));
-                            break;
-                        },
-                        Some(Block::Free) => continue,
-                        None => break,
-                    }
+    /// Move single blocks from the end of the disk to the first free space,
+    /// files are split up in the process
+    fn compress_blocks(mut self) -> Self {
+        let mut files = vec![];
+        while let Some(mut file) = self.files.pop() {
+            while let Some(free) = self
+                .free
+                .first_span(1)
+                .filter(|f| f.start < file.span.start)
+            {
+                let size = free.len().min(file.span.len());
+                self.free.remove(free.start..free.start + size);
+                self.free.insert(file.span.end - size..file.span.end);
+                files.push(File {
+                    id: file.id,
+                    span: free.start..free.start + size,
+                });
+                file.span.end -= size;
+                if file.span.is_empty() {
+                    break;
                 }
-            };
+            }
+            if !file.span.is_empty() {
+                files.push(file);
+            }
         }
-        Self { files: self.files.clone(), blocks }
+        self.files = files;
+        self
     }
 
+    /// Move whole files to the first free span they fit in, starting with the last file
     fn compress_files(mut self) -> Self {
-        for file in self.files.iter().rev() {
-            let target_size = file.size;
-            let mut start_index = 0;
-            let mut current_block_length = 0;
-            for (index, block) in self.blocks.iter().enumerate() {
-                match block {
-                    Block::File(f) => {
-                        if f.id == file.id {
-                            break; // We found ourselves, abort
-                        } else {
-                            current_block_length = 0;
-                            start_index = index + 1;
-                        }
-                    },
-                    Block::Free => {
-                        current_block_length += 1;
-                        if current_block_length == target_size {
-                            // Remove block from old position
-                            for block in self.blocks.iter_mut() {
-                                match block {
-                                    Block::File(f) => {
-                                        if f.id == file.id {
-                                            *block = Block::Free;
-                                        }
-                                    },
-                                    Block::Free => {},
-                                }
-                            }
-                            // Add block to new position
-                            for i in 0..target_size {
-                                self.blocks[start_index + i] = Block::File(file.clone());
-                            }
-                            break;
-                        }
-                    },
-                }
+        for file in self.files.iter_mut().rev() {
+            let size = file.span.len();
+            if let Some(free) = self
+                .free
+                .first_span(size)
+                .filter(|f| f.start < file.span.start)
+            {
+                let target = free.start..free.start + size;
+                self.free.remove(target.clone());
+                self.free.insert(file.span.clone());
+                file.span = target;
             }
         }
         self
     }
 
     fn checksum(&self) -> usize {
-        self.blocks.iter().enumerate().map(|(i, b)| match b {
-            Block::File(f) => f.id as usize * i,
-            Block::Free => 0,
-        }).sum()
+        self.files
+            .iter()
+            .map(|f| f.id * f.span.clone().sum::<usize>())
+            .sum()
     }
 }
 
@@ -108,64 +94,24 @@ impl FromStr for Disk {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut files = Vec::new();
-        let mut is_file = true;
-        let mut block_id = 0;
-        let blocks = s
-            .trim()
-            .chars()
-            .flat_map(|c| {
-                let size = c.to_digit(10).unwrap_or_else(|| panic!("Unexpected: {c}")) as usize;
-                let blocks = if is_file {
-                    let file = Rc::new(File { id: block_id, size });
-                    block_id += 1;
-                    files.push(file.clone());
-                    vec![Block::File(file); size]
-                } else {
-                    vec![Block::Free; size]
-                };
-                is_file = !is_file;
-                blocks
-            })
-            .collect::<Vec<_>>();
-
-        Ok(Self { files, blocks })
+        let mut free = RangeSet::new();
+        let mut position = 0;
+        for (i, c) in s.trim().chars().enumerate() {
+            let size = c.to_digit(10).unwrap_or_else(|| panic!("Unexpected: {c}")) as usize;
+            let span = position..position + size;
+            position += size;
+            if i % 2 == 0 {
+                files.push(File { id: i / 2, span });
+            } else {
+                free.insert(span);
+            }
+        }
+        Ok(Self { files, free })
     }
 }
 
-#[derive(Debug, Clone)]
-enum Block {
-    File(Rc<File>),
-    Free,
-}
-
 #[derive(Debug)]
 struct File {
-    id: u32,
-    size: usize,
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_puzzle_1() -> Puzzle {
-        Puzzle::from(r#"12345"#)
-    }
-    fn get_puzzle_2() -> Puzzle {
-        Puzzle::from(r#"2333133121414131402"#)
-    }
-
-    #[test]
-    fn part1() {
-        let result = Day::default().part1(&get_puzzle_1());
-        assert_eq!(result, 60.into());
-        let result = Day::default().part1(&get_puzzle_2());
-        assert_eq!(result, 1928.into());
-    }
-
-    #[test]
-    fn part2() {
-        let result = Day::default().part2(&get_puzzle_2());
-        assert_eq!(result, 2858.into());
-    }
+    id: usize,
+    span: Range<usize>,
 }
@@ -1,6 +1,13 @@
 use std::str::FromStr;
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{
+    aoc_puzzle,
+    tools::{
+        math::{egcd, solve_linear, LinearSolution},
+        parse::{literal, newline, uint, ParseError, Parser},
+    },
+    Answer, Puzzle, PuzzleSolution,
+};
 
 #[aoc_puzzle(day = 13)]
 #[derive(Default)]
@@ -38,18 +45,62 @@ struct Game {
 
 impl Game {
     fn solve(&self) -> Option<isize> {
-        let deter = self.a.0 * self.b.1 - self.a.1 * self.b.0;
-        let count_a = (self.prize.0 * self.b.1 - self.prize.1 * self.b.0) / deter;
-        let count_b = (self.a.0 * self.prize.1 - self.a.1 * self.prize.0) / deter;
-        if self.prize
-            == (
-                self.a.0 * count_a + self.b.0 * count_b,
-                self.a.1 * count_a + self.b.1 * count_b,
-            )
-        {
-            Some(count_a * 3 + count_b)
+        let presses = solve_linear(
+            &[
+                vec![self.a.0 as i128, self.b.0 as i128],
+                vec![self.a.1 as i128, self.b.1 as i128],
+            ],
+            &[self.prize.0 as i128, self.prize.1 as i128],
+        );
+        match presses {
+            LinearSolution::Unique(presses) => {
+                let count_a = presses[0].to_integer()?;
+                let count_b = presses[1].to_integer()?;
+                if count_a < 0 || count_b < 0 {
+                    return None;
+                }
+                Some((count_a * 3 + count_b) as isize)
+            }
+            LinearSolution::Infinite => self.solve_collinear(),
+            LinearSolution::None => None,
+        }
+    }
+    /// Cheapest presses when both buttons move in the same direction as the prize
+    ///
+    /// All presses `a * da + b * db = p` lie on a line `a = a0 + k * db / g`,
+    /// `b = b0 - k * da / g`, the cost `3a + b` changes linearly with `k` so the
+    /// cheapest presses are at one of the ends of the non-negative part.
+    fn solve_collinear(&self) -> Option<isize> {
+        let (da, db, p) = if self.a.0 != 0 || self.b.0 != 0 {
+            (self.a.0, self.b.0, self.prize.0)
+        } else if self.a.1 != 0 || self.b.1 != 0 {
+            (self.a.1, self.b.1, self.prize.1)
         } else {
-            None
+            // Neither button moves, the claw is already on the prize
+            return Some(0);
+        };
+        let cost = |a: i128, b: i128| (a * 3 + b) as isize;
+        match (da, db) {
+            (0, db) => (p % db == 0).then(|| cost(0, (p / db) as i128)),
+            (da, 0) => (p % da == 0).then(|| cost((p / da) as i128, 0)),
+            (da, db) => {
+                let (g, x, y) = egcd(da as i64, db as i64);
+                if p % g as isize != 0 {
+                    return None;
+                }
+                let (g, p) = (g as i128, p as i128);
+                let (a0, b0) = (x as i128 * p / g, y as i128 * p / g);
+                let (step_a, step_b) = (db as i128 / g, da as i128 / g);
+                let min_k = -a0.div_euclid(step_a);
+                let max_k = b0.div_euclid(step_b);
+                if min_k > max_k {
+                    return None;
+                }
+                [min_k, max_k]
+                    .into_iter()
+                    .map(|k| cost(a0 + k * step_a, b0 - k * step_b))
+                    .min()
+            }
         }
     }
     fn convert_unit(self) -> Self {
@@ -62,26 +113,31 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        let a = get_button_values(lines.next().unwrap());
-        let b = get_button_values(lines.next().unwrap());
-
-        let (_, vals) = lines.next().unwrap().split_once("X=").unwrap();
-        let (x, y) = vals.split_once(", Y=").unwrap();
-        let prize = (x.parse().unwrap(), y.parse().unwrap());
-        Ok(Game { prize, a, b })
+        let button = |name| {
+            literal("Button ")
+                .ignore_then(literal(name))
+                .ignore_then(literal(": X+"))
+                .ignore_then(uint())
+                .then_ignore(literal(", Y+"))
+                .then(uint())
+        };
+        let prize = literal("Prize: X=")
+            .ignore_then(uint())
+            .then_ignore(literal(", Y="))
+            .then(uint());
+        button("A")
+            .then_ignore(newline())
+            .then(button("B"))
+            .then_ignore(newline())
+            .then(prize)
+            .map(|((a, b), prize)| Game { prize, a, b })
+            .parse(s)
     }
 }
 
-fn get_button_values(val: &str) -> (isize, isize) {
-    let (_, vals) = val.split_once("X+").unwrap();
-    let (x, y) = vals.split_once(", Y+").unwrap();
-    (x.parse().unwrap(), y.parse().unwrap())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,13 +164,25 @@ Prize: X=18641, Y=10279"#,
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, 480.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&get_puzzle());
         assert_eq!(result, 875318608908_usize.into());
     }
+
+    #[test]
+    fn same_direction() {
+        let game = |a, b, prize| Game { prize, a, b };
+        assert_eq!(game((2, 2), (1, 1), (10, 10)).solve(), Some(10));
+        assert_eq!(game((4, 4), (1, 1), (8, 8)).solve(), Some(6));
+        assert_eq!(game((4, 4), (6, 6), (14, 14)).solve(), Some(7));
+        assert_eq!(game((4, 4), (6, 6), (24, 24)).solve(), Some(4));
+        assert_eq!(game((4, 4), (6, 6), (5, 5)).solve(), None);
+        assert_eq!(game((4, 4), (6, 6), (2, 2)).solve(), None);
+        assert_eq!(game((0, 3), (0, 1), (0, 9)).solve(), Some(9));
+    }
 }
@@ -1,16 +1,13 @@
-use std::str::FromStr;
+use std::{rc::Rc, str::FromStr};
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, tools::PeriodicComponents, Answer, Puzzle, PuzzleSolution};
 
-#[aoc_puzzle(day = 14)]
+// The part 2 example answer is just the outcome of this solution, the example has no tree
+#[aoc_puzzle(day = 14, examples = "src/solutions/examples/day14.txt")]
 pub struct Day {
+    #[param(default = (101, 103))]
     space: (i32, i32),
 }
-impl Default for Day {
-    fn default() -> Self {
-        Self { space: (101, 103) }
-    }
-}
 
 impl PuzzleSolution for Day {
     fn part1(&self, puzzle: &Puzzle) -> Answer {
@@ -44,42 +41,37 @@ impl PuzzleSolution for Day {
     }
 
     fn part2(&self, puzzle: &Puzzle) -> Answer {
-        let guards = puzzle
-            .get_input()
-            .lines()
-            .map(|s| s.parse::<Guard>().unwrap())
-            .collect::<Vec<_>>();
-        let (center_x, center_y) = ((self.space.0 - 1) / 2, (self.space.1 - 1) / 2);
-        // lets find the where most guards are closest to the center, no clue if this always works
-        let lowest = (0..10000)
-            .map(|i| {
-                let positions = guards
-                    .iter()
-                    .map(|g| g.get_position_at(i, self.space))
-                    .collect::<Vec<_>>();
-                let center_sum_x: usize = positions
-                    .iter()
-                    .map(|p| center_x.abs_diff(p.0) as usize)
-                    .sum::<usize>()
-                    / positions.len();
-                let center_sum_y: usize = positions
+        let guards = Rc::new(
+            puzzle
+                .get_input()
+                .lines()
+                .map(|s| s.parse::<Guard>().unwrap())
+                .collect::<Vec<_>>(),
+        );
+        if guards.is_empty() {
+            return Err::<usize, _>("No guards in the input").into();
+        }
+        let space = self.space;
+        let (center_x, center_y) = ((space.0 - 1) / 2, (space.1 - 1) / 2);
+        // The picture is where the guards are closest to the center. Both axes wrap independently,
+        // so score every second within the period of each axis by the mean distance to the center,
+        // and let the periodic components combine the best second of each axis with the CRT
+        let axis_score = move |guards: Rc<Vec<Guard>>, axis: fn((i32, i32)) -> i32, center: i32| {
+            move |second: usize| {
+                let distance: usize = guards
                     .iter()
-                    .map(|p| center_y.abs_diff(p.1) as usize)
-                    .sum::<usize>()
-                    / positions.len();
-                (i, center_sum_x + center_sum_y)
-            })
-            .min_by(|a, b| a.1.cmp(&b.1))
-            .unwrap();
-
-        // let mut grid: aoc_core::collections::HashGrid<i32, char> = Default::default();
-        // guards
-        //     .iter()
-        //     .map(|g| g.get_position_at(lowest.0, self.space))
-        //     .for_each(|(x, y)| { grid.insert(x, y, '#'); });
-        // grid.fill_empty('.');
-        // print!("{}\n", grid.to_string());
-        lowest.0.into()
+                    .map(|g| axis(g.get_position_at(second as i32, space)))
+                    .map(|p| center.abs_diff(p) as usize)
+                    .sum();
+                (distance / guards.len()) as i64
+            }
+        };
+        PeriodicComponents::new()
+            .with_component(space.0 as usize, axis_score(guards.clone(), |p| p.0, center_x))
+            .with_component(space.1 as usize, axis_score(guards.clone(), |p| p.1, center_y))
+            .first_step()
+            .ok_or("The axes never line up")
+            .into()
     }
 }
 
@@ -116,34 +108,12 @@ impl FromStr for Guard {
 mod tests {
     use super::*;
 
-    fn get_puzzle() -> Puzzle {
-        Puzzle::from(
-            r#"p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3"#,
-        )
-    }
-
-    #[test]
-    fn part1() {
-        let day = Day { space: (11, 7) };
-        let result = day.part1(&get_puzzle());
-        assert_eq!(result, 12.into());
-    }
-
     #[test]
-    fn part2() {
-        let result = Day::default().part2(&get_puzzle());
-         // Just the outcome for the test for my solution
-        assert_eq!(result, 1976.into());
+    fn register_configured() {
+        let mut collection = aoc_core::SolutionCollection::default();
+        register_solution_with(&mut collection, Day { space: (11, 7) });
+        let params = collection.get_params(&14);
+        assert_eq!(params[0].value, "11,7");
+        assert_eq!(params[0].default, "101,103");
     }
 }
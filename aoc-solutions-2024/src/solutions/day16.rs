@@ -1,6 +1,10 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use aoc_core::{aoc_puzzle, tools::Grid, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{
+    aoc_puzzle,
+    tools::{Grid, GridCell},
+    Answer, Puzzle, PuzzleSolution,
+};
 
 #[aoc_puzzle(day = 16)]
 #[derive(Default)]
@@ -75,13 +79,11 @@ fn create_distance_map_with_dijkstra(
     DistanceMap,
     (usize, usize, Direction),
 ) {
-    let mut grid: Grid<usize, char> = input.into();
-    let start = grid.iter().find(|(_, &c)| c == 'S').unwrap().0;
-    let start = (*start.0, *start.1, Direction::Right);
-    let end = grid.iter().find(|(_, &c)| c == 'E').unwrap().0;
-    let end = (*end.0, *end.1);
-    grid.insert(start.0, start.1, '.');
-    grid.insert(end.0, end.1, '.');
+    let (grid, markers) =
+        Grid::parse_with_markers(&input, &['S', 'E'], Tile::Open).expect("Valid maze");
+    let start = markers.get('S').expect("Start");
+    let start = (start.0, start.1, Direction::Right);
+    let end = markers.get('E').expect("End");
 
     let mut dist_map = HashMap::from([((start.0, start.1), HashMap::from([(start.2, 0)]))]);
     let mut queue = VecDeque::from([(start.0, start.1, start.2, 0)]);
@@ -98,7 +100,7 @@ fn create_distance_map_with_dijkstra(
         next_coords
             .iter()
             .filter_map(|c| Some((c.0?, c.1?, c.2, dir.turn_cost(&c.2)?)))
-            .filter(|(dx, dy, _, _)| grid.get(*dx, *dy) == Some(&'.'))
+            .filter(|(dx, dy, _, _)| grid.get(*dx, *dy) == Some(&Tile::Open))
             .for_each(|(dx, dy, new_dir, turn_cost)| {
                 let move_cost = turn_cost + d + 1;
                 let current_cost = dist_map.get_mut(&(dx, dy));
@@ -122,6 +124,14 @@ fn create_distance_map_with_dijkstra(
     (dist_map, (end.0, end.1, best_direction))
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, GridCell)]
+enum Tile {
+    #[cell('#')]
+    Wall,
+    #[cell('.')]
+    Open,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
     Up = 0,
@@ -168,13 +178,13 @@ mod tests {
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, 7036.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&get_puzzle());
         assert_eq!(result, 45.into());
     }
 }
@@ -1,6 +1,10 @@
 use std::str::FromStr;
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{
+    aoc_puzzle,
+    tools::parse::{blank_line, literal, newline, uint, ParseError, Parser},
+    Answer, Puzzle, PuzzleSolution,
+};
 
 #[aoc_puzzle(day = 17)]
 #[derive(Default)]
@@ -126,22 +130,31 @@ impl Program {
 }
 
 impl FromStr for Program {
-    type Err = Box<dyn std::error::Error>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (registers, instructions) = s.split_once("\n\n").ok_or("Could not split")?;
-        let registers: Registers = registers.parse()?;
-        let program = instructions
-            .strip_prefix("Program: ")
-            .ok_or("Missing prefix")?
-            .trim()
-            .split(',')
-            .map(|s| s.parse::<usize>().unwrap())
-            .collect::<Vec<_>>();
-        Ok(Self {
-            instructions: program,
-            registers,
-        })
+        let register = |name| {
+            literal("Register ")
+                .ignore_then(literal(name))
+                .ignore_then(literal(": "))
+                .ignore_then(uint())
+        };
+        let registers = register("A")
+            .then_ignore(newline())
+            .then(register("B"))
+            .then_ignore(newline())
+            .then(register("C"))
+            .map(|((a, b), c)| Registers::new(a, b, c));
+        let instructions =
+            literal("Program: ").ignore_then(uint().separated_by(literal(",")));
+        registers
+            .then_ignore(blank_line())
+            .then(instructions)
+            .map(|(registers, instructions)| Self {
+                instructions,
+                registers,
+            })
+            .parse(s)
     }
 }
 
@@ -184,30 +197,6 @@ impl Registers {
     }
 }
 
-impl FromStr for Registers {
-    type Err = Box<dyn std::error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut a = 0;
-        let mut b = 0;
-        let mut c = 0;
-        for line in s.lines() {
-            let (reg, val) = line
-                .strip_prefix("Register ")
-                .ok_or("Missing prefix")?
-                .split_once(": ")
-                .ok_or("Missing delimiter")?;
-            match reg {
-                "A" => a = val.parse()?,
-                "B" => b = val.parse()?,
-                "C" => c = val.parse()?,
-                _ => unreachable!(),
-            }
-        }
-        Ok(Self::new(a, b, c))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,13 +224,13 @@ Program: 0,3,5,4,3,0"#,
     
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, "4,6,3,5,6,3,5,2,1,0".into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle_b());
+        let result = Day.part2(&get_puzzle_b());
         assert_eq!(result, 117440.into());
     }
 
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, tools::Memo, Answer, Puzzle, PuzzleSolution};
 
 #[aoc_puzzle(day = 19)]
 #[derive(Default)]
@@ -9,20 +7,20 @@ pub struct Day;
 impl PuzzleSolution for Day {
     fn part1(&self, puzzle: &Puzzle) -> Answer {
         let (towels, designs) = parse(puzzle.input_as_str());
-        let mut counter = DesignCounter::new();
+        let mut memo = Memo::new();
         designs
             .into_iter()
-            .filter_map(|design| (counter.count_designs(design, &towels) > 0).then_some(()))
+            .filter_map(|design| (count_designs(&mut memo, &design, &towels) > 0).then_some(()))
             .count()
             .into()
     }
 
     fn part2(&self, puzzle: &Puzzle) -> Answer {
         let (towels, designs) = parse(puzzle.input_as_str());
-        let mut counter = DesignCounter::new();
+        let mut memo = Memo::new();
         designs
             .into_iter()
-            .map(|design| counter.count_designs(design, &towels))
+            .map(|design| count_designs(&mut memo, &design, &towels))
             .sum::<usize>()
             .into()
     }
@@ -41,41 +39,21 @@ fn parse(input: &str) -> (Vec<Vec<char>>, Vec<Vec<char>>) {
     (towels, designs)
 }
 
-struct DesignCounter {
-    memo: HashMap<Vec<char>, usize>,
-}
-
-impl DesignCounter {
-    fn new () -> Self {
-        Self {
-            memo: HashMap::new(),
-        }
+fn count_designs(
+    memo: &mut Memo<Vec<char>, usize>,
+    design: &[char],
+    towels: &[Vec<char>],
+) -> usize {
+    if design.is_empty() {
+        return 1;
     }
-    fn count_designs(&mut self, full_design: Vec<char>, towels: &Vec<Vec<char>>) -> usize {
-        self.count_designs_recursive(&full_design, towels)
-    }
-
-    #[inline]
-    fn count_designs_recursive(&mut self, design: &[char], towels: &Vec<Vec<char>>) -> usize {
-        if design.is_empty() {
-            return 1;
-        }
-        if let Some(c) = self.memo.get(design) {
-            return *c;
-        }
-        let count = towels
+    memo.get_or_compute(design, |memo| {
+        towels
             .iter()
-            .filter_map(|towel| {
-                if towel.len() <= design.len() && *towel == design[..towel.len()] {
-                    Some(self.count_designs_recursive(&design[towel.len()..], towels))
-                } else {
-                    None
-                }
-            })
-            .sum();
-        self.memo.insert(design.to_vec(), count);
-        count
-    }
+            .filter(|towel| design.starts_with(towel))
+            .map(|towel| count_designs(memo, &design[towel.len()..], towels))
+            .sum()
+    })
 }
 
 #[cfg(test)]
@@ -99,13 +77,13 @@ bbrgwb"#,
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, 6.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&get_puzzle());
         assert_eq!(result, 16.into());
     }
 }
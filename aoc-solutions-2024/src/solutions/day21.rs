@@ -1,6 +1,6 @@
 use std::{collections::HashMap, fmt::{Debug, Write}};
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, span, Answer, Puzzle, PuzzleSolution};
 
 #[aoc_puzzle(day = 21)]
 #[derive(Default)]
@@ -130,6 +130,7 @@ impl Keypad {
     }
 
     fn get_own_moves_to(&mut self, target_char: char) -> Vec<Move> {
+        let _span = span!("get_own_moves_to");
         let target = self.field_map.get(&target_char).unwrap();
         let mut current = self.position;
         let mut moves = vec![];
@@ -163,6 +164,7 @@ impl Keypad {
     }
 
     fn get_moves_to(&mut self, value: char) -> HashMap<Vec<Move>, usize> {
+        let _span = span!("get_moves_to");
         let mut move_chunks: HashMap<Vec<Move>, usize> = HashMap::new();
         if let Some(next) = self.next.as_mut() {
             let child_move_map = next.get_moves_to(value);
@@ -247,13 +249,13 @@ mod tests {
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&get_puzzle());
         assert_eq!(result, 126384.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&get_puzzle());
         assert_eq!(result, 154115708116294_usize.into());
     }
 }
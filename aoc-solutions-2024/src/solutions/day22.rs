@@ -100,13 +100,13 @@ mod tests {
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle_p1());
+        let result = Day.part1(&get_puzzle_p1());
         assert_eq!(result, 37327623.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle_p2());
+        let result = Day.part2(&get_puzzle_p2());
         assert_eq!(result, 23.into());
     }
 }
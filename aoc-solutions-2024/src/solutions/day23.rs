@@ -1,23 +1,37 @@
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{aoc_puzzle, Answer, ParsedPuzzleSolution, Puzzle};
 use std::collections::{HashMap, HashSet};
 
 #[aoc_puzzle(day = 23)]
 #[derive(Default)]
 pub struct Day;
 
-impl PuzzleSolution for Day {
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
-        let connections = puzzle
-            .input_as_str()
-            .lines()
-            .map(|l| l.split_once('-').unwrap())
-            .fold(HashMap::<&str, HashSet<&str>>::new(), |mut map, set| {
-                map.entry(set.0).or_default().insert(set.1);
-                map.entry(set.1).or_default().insert(set.0);
-                map
-            });
+/// Computers connected to each computer
+type Connections = HashMap<String, HashSet<String>>;
+
+impl ParsedPuzzleSolution for Day {
+    type Parsed = Connections;
+
+    fn parse(&self, puzzle: &Puzzle) -> Result<Connections, Box<dyn std::error::Error>> {
+        let mut connections = Connections::new();
+        for line in puzzle.input_as_str().lines() {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| format!("Invalid connection '{}'", line))?;
+            connections
+                .entry(a.to_string())
+                .or_default()
+                .insert(b.to_string());
+            connections
+                .entry(b.to_string())
+                .or_default()
+                .insert(a.to_string());
+        }
+        Ok(connections)
+    }
+
+    fn part1(&self, connections: &Connections) -> Answer {
         let mut clusters = HashSet::new();
-        for (c1, c1_connections) in &connections { 
+        for (c1, c1_connections) in connections { 
             for c2 in c1_connections.iter() {
                 if let Some(c2_connections) = connections.get(c2) {
                     for c3 in c2_connections {
@@ -25,7 +39,7 @@ impl PuzzleSolution for Day {
                             continue;
                         }
                         if c1_connections.contains(c3) {
-                            let mut set = vec![c1, c2, *c3];
+                            let mut set = vec![c1, c2, c3];
                             set.sort_unstable();
                             clusters.insert(set);
                         }
@@ -40,25 +54,16 @@ impl PuzzleSolution for Day {
             .into()
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
-        let connections = puzzle
-            .input_as_str()
-            .lines()
-            .map(|l| l.split_once('-').unwrap())
-            .fold(HashMap::<&str, HashSet<&str>>::new(), |mut map, set| {
-                map.entry(set.0).or_default().insert(set.1);
-                map.entry(set.1).or_default().insert(set.0);
-                map
-            });
+    fn part2(&self, connections: &Connections) -> Answer {
         let mut clusters = HashSet::new();
-        for (c1, c1_connections) in &connections { 
+        for (c1, c1_connections) in connections { 
             for c2 in c1_connections.iter() {
                 if let Some(c2_connections) = connections.get(c2) {
-                    let mut network = HashSet::from([*c1, *c2]);
+                    let mut network = HashSet::from([c1.as_str(), c2.as_str()]);
                     let connected_to_both = c1_connections.intersection(c2_connections);
                     for c3 in connected_to_both {
                         let c3_connections = connections.get(c3).unwrap();
-                        if c3_connections.intersection(&network).count() == network.len() {
+                        if network.iter().all(|c| c3_connections.contains(*c)) {
                             network.insert(c3);
                         }
                     }
@@ -115,13 +120,13 @@ td-yn"#,
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&Day.parse(&get_puzzle()).unwrap());
         assert_eq!(result, 7.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&Day.parse(&get_puzzle()).unwrap());
         assert_eq!(result, "co,de,ka,ta".into());
     }
 }
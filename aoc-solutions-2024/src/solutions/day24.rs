@@ -1,30 +1,28 @@
-use std::{collections::{HashMap, HashSet}, rc::Rc, str::FromStr};
+use std::collections::{HashMap, HashSet};
 
-use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
+use aoc_core::{
+    aoc_puzzle, span,
+    tools::parse::{blank_line, identifier, lines, literal, ParseError, Parser},
+    Answer, ParsedPuzzleSolution, Puzzle,
+};
 
 #[aoc_puzzle(day = 24)]
 #[derive(Default)]
 pub struct Day;
 
-impl PuzzleSolution for Day {
-    fn part1(&self, puzzle: &Puzzle) -> Answer {
-        let (start_values, gates) = puzzle.input_as_str().split_once("\n\n").unwrap();
-        let gates = gates
-            .lines()
-            .map(|l| Rc::new(l.parse::<Gate>().unwrap()))
-            .map(|g| (g.output.clone(), g))
-            .collect::<HashMap<_, _>>();
-        let mut states: HashMap<String, bool> = start_values
-            .lines()
-            .map(|l| {
-                let parts = l.split_once(": ").unwrap();
-                (parts.0.to_string(), parts.1 == "1")
-            })
-            .collect::<HashMap<_, _>>();
+impl ParsedPuzzleSolution for Day {
+    type Parsed = Circuit;
+
+    fn parse(&self, puzzle: &Puzzle) -> Result<Circuit, Box<dyn std::error::Error>> {
+        Ok(parse_circuit(puzzle.input_as_str())?)
+    }
+
+    fn part1(&self, (states, gates): &Circuit) -> Answer {
+        let mut states = states.clone();
         let mut z_states = vec![];
         for gate in gates.values() {
             if gate.output.starts_with("z") {
-                let out = resolve_state(&gates, &mut states, gate.output.clone());
+                let out = resolve_state(gates, &mut states, gate.output.clone());
                 z_states.push((gate.output.clone(), out));
             }
         }
@@ -43,13 +41,7 @@ impl PuzzleSolution for Day {
         number.into()
     }
 
-    fn part2(&self, puzzle: &Puzzle) -> Answer {
-        let (_, gates) = puzzle.input_as_str().split_once("\n\n").unwrap();
-        let gates = gates
-            .lines()
-            .map(|l| Rc::new(l.parse::<Gate>().unwrap()))
-            .map(|g| (g.output.clone(), g))
-            .collect::<HashMap<_, _>>();
+    fn part2(&self, (_, gates): &Circuit) -> Answer {
         let input_count = gates
             .values()
             .filter_map(|g| {
@@ -104,12 +96,11 @@ impl PuzzleSolution for Day {
                 } else {
                     problems.insert(xor2.output.clone());
                 }
-            } else if and1.is_none() || xor1.is_none() {
-                // x/y not linked to and1 and xor1, this does not happen in my input
-                panic!("Problem found with gate: x{:02}/y{:02}, not linked to and1 and xor1", d, d);
             } else {
-                let and1 = and1.unwrap();
-                let xor1 = xor1.unwrap();                
+                let (Some(and1), Some(xor1)) = (and1, xor1) else {
+                    // x/y not linked to and1 and xor1, this does not happen in my input
+                    panic!("Problem found with gate: x{:02}/y{:02}, not linked to and1 and xor1", d, d);
+                };
                 let xor2 = gates.values().find(|g| g.operator == Operator::Xor && g.takes_input(&xor1.output));
                 let and2 = gates.values().find(|g| g.operator == Operator::And && g.takes_input(&xor1.output));
                 let or1 = gates.values().find(|g| g.operator == Operator::Or && g.takes_input(&and1.output));
@@ -141,10 +132,11 @@ impl PuzzleSolution for Day {
 }
 
 fn resolve_state(
-    gates: &HashMap<String, Rc<Gate>>,
+    gates: &HashMap<String, Gate>,
     states: &mut HashMap<String, bool>,
     output: String,
 ) -> bool {
+    let _span = span!("resolve_state");
     if let Some(state) = states.get(&output) {
         return *state;
     }
@@ -177,7 +169,7 @@ impl std::fmt::Display for Operator {
     }
 }
 
-struct Gate {
+pub struct Gate {
     left: String,
     right: String,
     output: String,
@@ -190,27 +182,40 @@ impl Gate {
     }
 }
 
-impl FromStr for Gate {
-    type Err = ();
+/// Initial wire values and the gates by output wire
+type Circuit = (HashMap<String, bool>, HashMap<String, Gate>);
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split_whitespace().collect::<Vec<_>>();
-        let left = parts[0].to_string();
-        let operator = match parts[1] {
-            "AND" => Operator::And,
-            "OR" => Operator::Or,
-            "XOR" => Operator::Xor,
-            _ => panic!("Invalid operator"),
-        };
-        let right = parts[2].to_string();
-        let output = parts[4].to_string();
-        Ok(Gate {
+fn parse_circuit(input: &str) -> Result<Circuit, ParseError> {
+    let bit = literal("1").map(|_| true).or(literal("0").map(|_| false));
+    let value = identifier().then_ignore(literal(": ")).then(bit);
+    let operator = literal("AND")
+        .map(|_| Operator::And)
+        .or(literal("OR").map(|_| Operator::Or))
+        .or(literal("XOR").map(|_| Operator::Xor));
+    let gate = identifier()
+        .then_ignore(literal(" "))
+        .then(operator)
+        .then_ignore(literal(" "))
+        .then(identifier())
+        .then_ignore(literal(" -> "))
+        .then(identifier())
+        .map(|(((left, operator), right), output)| Gate {
             left,
             right,
             output,
             operator,
+        });
+    lines(value)
+        .then_ignore(blank_line())
+        .then(lines(gate))
+        .map(|(values, gates)| {
+            let gates = gates
+                .into_iter()
+                .map(|g| (g.output.clone(), g))
+                .collect();
+            (values.into_iter().collect(), gates)
         })
-    }
+        .parse(input)
 }
 
 #[cfg(test)]
@@ -271,13 +276,13 @@ tnw OR pbm -> gnj"#,
 
     #[test]
     fn part1() {
-        let result = Day::default().part1(&get_puzzle());
+        let result = Day.part1(&Day.parse(&get_puzzle()).unwrap());
         assert_eq!(result, 2024.into());
     }
 
     #[test]
     fn part2() {
-        let result = Day::default().part2(&get_puzzle());
+        let result = Day.part2(&Day.parse(&get_puzzle()).unwrap());
         assert_eq!(result, 0.into());
     }
 }
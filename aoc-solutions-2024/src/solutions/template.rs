@@ -1,6 +1,6 @@
 use aoc_core::{aoc_puzzle, Answer, Puzzle, PuzzleSolution};
 
-#[aoc_puzzle(day = _)]
+#[aoc_puzzle(day = _, example(input = r#""#, part1 = 0, part2 = 0))]
 #[derive(Default)]
 pub struct Day;
 
@@ -13,24 +13,3 @@ impl PuzzleSolution for Day {
         0.into()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_puzzle() -> Puzzle {
-        Puzzle::from(r#""#)
-    }
-
-    #[test]
-    fn part1() {
-        let result = Day::default().part1(&get_puzzle());
-        assert_eq!(result, 0.into());
-    }
-
-    #[test]
-    fn part2() {
-        let result = Day::default().part2(&get_puzzle());
-        assert_eq!(result, 0.into());
-    }
-}
\ No newline at end of file